@@ -1,6 +1,6 @@
 use nft_runtime::{
-    AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-    SystemConfig, WASM_BINARY,
+    AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, NftConfig, Signature,
+    SudoConfig, SystemConfig, WASM_BINARY,
 };
 use nft_runtime::{ContractsConfig, ContractsSchedule};
 use sc_service::ChainType;
@@ -134,5 +134,6 @@ fn testnet_genesis(
                 ..Default::default()
             },
         }),
+        nft: Some(NftConfig {}),
     }
 }
@@ -0,0 +1,17 @@
+;; Accepts every item: `on_nft_received` returns its selector
+(module
+	(import "env" "ext_return" (func $ext_return (param i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $ext_return
+			(i32.const 0) ;; Pointer to the selector
+			(i32.const 4) ;; Length of the selector
+		)
+		(unreachable)
+	)
+
+	(data (i32.const 0) "\91\b2\a4\98")
+)
@@ -0,0 +1,10 @@
+;; Runs out of gas in `on_nft_received`
+(module
+	(import "env" "memory" (memory 1 1))
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(loop $inf (br $inf))
+	)
+)
@@ -0,0 +1,17 @@
+;; Rejects every item: `on_nft_received` succeeds, but returns other bytes
+(module
+	(import "env" "ext_return" (func $ext_return (param i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $ext_return
+			(i32.const 0) ;; Pointer to the output
+			(i32.const 4) ;; Length of the output
+		)
+		(unreachable)
+	)
+
+	(data (i32.const 0) "\00\00\00\00")
+)
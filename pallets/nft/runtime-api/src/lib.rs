@@ -0,0 +1,14 @@
+//! Runtime API definition of the nft pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+    pub trait NftApi<AccountId> where AccountId: Codec {
+        /// Number of prepaid transactions left to the collection in Resource Purchase fee model.
+        fn resource_quota(collection_id: u64) -> u64;
+        /// Amount of the item `spender` can transfer on behalf of `owner`.
+        fn allowance(collection_id: u64, item_id: u64, owner: AccountId, spender: AccountId) -> u64;
+    }
+}
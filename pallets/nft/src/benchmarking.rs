@@ -0,0 +1,578 @@
+//! Benchmarks for the nft pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, account};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::vec;
+
+const SEED: u32 = 1;
+
+fn create_collection_helper<T: Trait>(owner: T::AccountId, mode: CollectionMode) -> u64 {
+    let col_name: Vec<u16> = "Test1".encode_utf16().collect::<Vec<u16>>();
+    let col_desc: Vec<u16> = "TestDescription1".encode_utf16().collect::<Vec<u16>>();
+    let token_prefix: Vec<u8> = b"token_prefix1".to_vec();
+
+    Module::<T>::create_collection(RawOrigin::Signed(owner).into(), col_name, col_desc, token_prefix, mode)
+        .expect("collection is created");
+    NextCollectionID::get()
+}
+
+fn create_item_helper<T: Trait>(owner: T::AccountId, collection_id: u64, data_size: u32) -> u64 {
+    let data = vec![0u8; data_size as usize];
+    Module::<T>::create_item(RawOrigin::Signed(owner.clone()).into(), collection_id, data, owner)
+        .expect("item is created");
+    <ItemListIndex>::get(collection_id)
+}
+
+// Approves `count` accounts to transfer the item of `owner`.
+fn approve_helper<T: Trait>(owner: T::AccountId, collection_id: u64, item_id: u64, count: u32) {
+    for i in 0 .. count {
+        let approved: T::AccountId = account("approved", i + 1, SEED);
+        Module::<T>::approve(RawOrigin::Signed(owner.clone()).into(), approved, collection_id, item_id, 1)
+            .expect("account is approved");
+    }
+}
+
+// Splits a re-fungible item of `owner` between `o` owners in total.
+fn split_item_helper<T: Trait>(owner: T::AccountId, collection_id: u64, item_id: u64, o: u32) {
+    for i in 1 .. o {
+        let recipient: T::AccountId = account("owner", i, SEED);
+        Module::<T>::transfer(RawOrigin::Signed(owner.clone()).into(), recipient, collection_id, item_id, 1)
+            .expect("item part is transferred");
+    }
+}
+
+// Lets other owners of a re-fungible item of `owner` approve `l` accounts in
+// total, MAX_APPROVED_PER_OWNER accounts per owner.
+fn fill_approved_list_helper<T: Trait>(owner: T::AccountId, collection_id: u64, item_id: u64, l: u32) {
+    let owners = (l + MAX_APPROVED_PER_OWNER - 1) / MAX_APPROVED_PER_OWNER;
+    split_item_helper::<T>(owner, collection_id, item_id, owners + 1);
+    for i in 0 .. l {
+        let approver: T::AccountId = account("owner", i / MAX_APPROVED_PER_OWNER + 1, SEED);
+        let approved: T::AccountId = account("approved_by_owner", i, SEED);
+        Module::<T>::approve(RawOrigin::Signed(approver).into(), approved, collection_id, item_id, 1)
+            .expect("account is approved");
+    }
+}
+
+benchmarks! {
+    _ {
+        let a in 0 .. MAX_COLLECTION_ADMINS - 1 => ();
+        let b in 0 .. 2048 => ();
+        let i in 0 .. COLLECTION_CLEANUP_ITEMS_LIMIT - 1 => ();
+        let o in 1 .. MAX_REFUNGIBLE_OWNERS - 1 => ();
+        let n in 1 .. MAX_ITEMS_PER_BATCH => ();
+        let s in 0 .. 2048 * MAX_ITEMS_PER_BATCH => ();
+        let l in 0 .. MAX_APPROVED_PER_ITEM - MAX_APPROVED_PER_OWNER => ();
+    }
+
+    create_collection {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let col_name: Vec<u16> = "Test1".encode_utf16().collect::<Vec<u16>>();
+        let col_desc: Vec<u16> = "TestDescription1".encode_utf16().collect::<Vec<u16>>();
+        let token_prefix: Vec<u8> = b"token_prefix1".to_vec();
+    }: _(RawOrigin::Signed(caller.clone()), col_name, col_desc, token_prefix, CollectionMode::NFT(2048))
+    verify {
+        assert_eq!(<Collection<T>>::get(NextCollectionID::get()).owner, caller);
+    }
+
+    // Worst case: `i` re-fungible items, the whole collection is removed in the call.
+    destroy_collection {
+        let i in ...;
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::ReFungible(2048, 3));
+        for _ in 0 .. i {
+            create_item_helper::<T>(caller.clone(), collection_id, 2048);
+        }
+    }: _(RawOrigin::Signed(caller), collection_id)
+    verify {
+        assert!(!<Collection<T>>::contains_key(collection_id));
+        assert!(!<DestroyingCollection>::contains_key(collection_id));
+    }
+
+    change_collection_owner {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let new_owner: T::AccountId = account("new_owner", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+    }: _(RawOrigin::Signed(caller), collection_id, new_owner.clone())
+    verify {
+        assert_eq!(<Collection<T>>::get(collection_id).owner, new_owner);
+    }
+
+    add_collection_admin {
+        let a in ...;
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+        for i in 0 .. a {
+            let admin: T::AccountId = account("admin", i, SEED);
+            Module::<T>::add_collection_admin(RawOrigin::Signed(caller.clone()).into(), collection_id, admin)?;
+        }
+        let new_admin: T::AccountId = account("admin", a, SEED);
+    }: _(RawOrigin::Signed(caller), collection_id, new_admin.clone())
+    verify {
+        assert!(<AdminList<T>>::get(collection_id).contains(&new_admin));
+    }
+
+    remove_collection_admin {
+        let a in ...;
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+        for i in 0 ..= a {
+            let admin: T::AccountId = account("admin", i, SEED);
+            Module::<T>::add_collection_admin(RawOrigin::Signed(caller.clone()).into(), collection_id, admin)?;
+        }
+        let admin: T::AccountId = account("admin", a, SEED);
+    }: _(RawOrigin::Signed(caller), collection_id, admin.clone())
+    verify {
+        assert!(!<AdminList<T>>::get(collection_id).contains(&admin));
+    }
+
+    set_collection_sponsor {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let sponsor: T::AccountId = account("sponsor", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+    }: _(RawOrigin::Signed(caller), collection_id, sponsor.clone())
+    verify {
+        assert_eq!(<Collection<T>>::get(collection_id).unconfirmed_sponsor, sponsor);
+    }
+
+    confirm_sponsorship {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let sponsor: T::AccountId = account("sponsor", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+        Module::<T>::set_collection_sponsor(RawOrigin::Signed(caller).into(), collection_id, sponsor.clone())?;
+    }: _(RawOrigin::Signed(sponsor.clone()), collection_id)
+    verify {
+        assert_eq!(<Collection<T>>::get(collection_id).sponsor, sponsor);
+    }
+
+    remove_collection_sponsor {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let sponsor: T::AccountId = account("sponsor", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+        Module::<T>::set_collection_sponsor(RawOrigin::Signed(caller.clone()).into(), collection_id, sponsor.clone())?;
+        Module::<T>::confirm_sponsorship(RawOrigin::Signed(sponsor).into(), collection_id)?;
+    }: _(RawOrigin::Signed(caller), collection_id)
+    verify {
+        assert_eq!(<Collection<T>>::get(collection_id).sponsor, T::AccountId::default());
+    }
+
+    // Worst case: a white listed non-admin account mints with a per account limit.
+    create_item {
+        let b in ...;
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let collection_id = create_collection_helper::<T>(owner.clone(), CollectionMode::NFT(2048));
+        Module::<T>::set_public_access_mode(RawOrigin::Signed(owner.clone()).into(), collection_id, AccessMode::WhiteList)?;
+        Module::<T>::add_to_white_list(RawOrigin::Signed(owner.clone()).into(), collection_id, caller.clone())?;
+        Module::<T>::set_mint_permission(RawOrigin::Signed(owner).into(), collection_id, true, 10)?;
+        let data = vec![0u8; b as usize];
+    }: _(RawOrigin::Signed(caller.clone()), collection_id, data, caller.clone())
+    verify {
+        assert_eq!(<Balance<T>>::get(collection_id, caller.clone()), 1);
+        assert_eq!(<AccountMinted<T>>::get(collection_id, caller), 1);
+    }
+
+    // Worst case: every item has its own owner. Items share `s` bytes of data.
+    create_multiple_items {
+        let n in ...;
+        let s in ...;
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048 * MAX_ITEMS_PER_BATCH));
+        let items: Vec<(Vec<u8>, T::AccountId)> = (0..n)
+            .map(|i| (vec![0u8; (s / n + if i < s % n { 1 } else { 0 }) as usize], account("owner", i, SEED)))
+            .collect();
+    }: _(RawOrigin::Signed(caller), collection_id, items)
+    verify {
+        assert_eq!(<ItemListIndex>::get(collection_id), n as u64);
+    }
+
+    burn_item {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+        let item_id = create_item_helper::<T>(caller.clone(), collection_id, 2048);
+    }: _(RawOrigin::Signed(caller.clone()), collection_id, item_id)
+    verify {
+        assert_eq!(<Balance<T>>::get(collection_id, caller), 0);
+    }
+
+    // Worst case: the recipient becomes a new owner of a re-fungible item shared by `o` owners.
+    transfer {
+        let o in ...;
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::ReFungible(2048, 3));
+        let item_id = create_item_helper::<T>(caller.clone(), collection_id, 2048);
+        split_item_helper::<T>(caller.clone(), collection_id, item_id, o);
+    }: _(RawOrigin::Signed(caller), recipient.clone(), collection_id, item_id, 1)
+    verify {
+        assert_eq!(<Balance<T>>::get(collection_id, recipient), 1);
+    }
+
+    // Worst case: other owners approved `l` accounts, the sender almost
+    // reached its limit.
+    approve {
+        let l in ...;
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let approved: T::AccountId = account("approved", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::ReFungible(2048, 3));
+        let item_id = create_item_helper::<T>(caller.clone(), collection_id, 2048);
+        fill_approved_list_helper::<T>(caller.clone(), collection_id, item_id, l);
+        approve_helper::<T>(caller.clone(), collection_id, item_id, MAX_APPROVED_PER_OWNER - 1);
+    }: _(RawOrigin::Signed(caller.clone()), approved.clone(), collection_id, item_id, 1)
+    verify {
+        assert!(<ApprovedList<T>>::get(collection_id, item_id).contains(&approved));
+        assert_eq!(Module::<T>::allowance(collection_id, item_id, &caller, &approved), 1);
+    }
+
+    // Worst case: the account has almost all operators.
+    set_approval_for_all {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let operator: T::AccountId = account("operator", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+        for i in 1 .. MAX_OPERATORS_PER_ACCOUNT {
+            Module::<T>::set_approval_for_all(RawOrigin::Signed(caller.clone()).into(), collection_id, account("operator", i, SEED), true)?;
+        }
+    }: _(RawOrigin::Signed(caller.clone()), collection_id, operator.clone(), true)
+    verify {
+        assert!(Module::<T>::is_approved_for_all(collection_id, &caller, &operator));
+    }
+
+    // Worst case: other owners approved `l` accounts, the revoked account
+    // leaves the approved list.
+    revoke_approval {
+        let l in ...;
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let approved: T::AccountId = account("approved", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::ReFungible(2048, 3));
+        let item_id = create_item_helper::<T>(caller.clone(), collection_id, 2048);
+        fill_approved_list_helper::<T>(caller.clone(), collection_id, item_id, l);
+        approve_helper::<T>(caller.clone(), collection_id, item_id, MAX_APPROVED_PER_OWNER - 1);
+        Module::<T>::approve(RawOrigin::Signed(caller.clone()).into(), approved.clone(), collection_id, item_id, 1)?;
+    }: _(RawOrigin::Signed(caller.clone()), approved.clone(), collection_id, item_id)
+    verify {
+        assert!(!<ApprovedList<T>>::get(collection_id, item_id).contains(&approved));
+        assert_eq!(Module::<T>::allowance(collection_id, item_id, &caller, &approved), 0);
+    }
+
+    // Worst case: other owners approved `l` accounts, every account of the
+    // sender leaves the approved list.
+    clear_approvals {
+        let l in ...;
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::ReFungible(2048, 3));
+        let item_id = create_item_helper::<T>(caller.clone(), collection_id, 2048);
+        fill_approved_list_helper::<T>(caller.clone(), collection_id, item_id, l);
+        approve_helper::<T>(caller.clone(), collection_id, item_id, MAX_APPROVED_PER_OWNER);
+    }: _(RawOrigin::Signed(caller.clone()), collection_id, item_id)
+    verify {
+        assert!(!<OwnerApprovedList<T>>::contains_key((collection_id, item_id), &caller));
+        assert_eq!(<ApprovedList<T>>::get(collection_id, item_id).len(), l as usize);
+    }
+
+    transfer_from {
+        let o in ...;
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let spender: T::AccountId = account("spender", 0, SEED);
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::ReFungible(2048, 3));
+        let item_id = create_item_helper::<T>(caller.clone(), collection_id, 2048);
+        split_item_helper::<T>(caller.clone(), collection_id, item_id, o);
+        Module::<T>::approve(RawOrigin::Signed(caller.clone()).into(), spender.clone(), collection_id, item_id, 1)?;
+    }: _(RawOrigin::Signed(spender), caller.clone(), recipient.clone(), collection_id, item_id, 1)
+    verify {
+        assert_eq!(<Balance<T>>::get(collection_id, recipient), 1);
+    }
+
+    safe_transfer_from {
+        let o in ...;
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let spender: T::AccountId = account("spender", 0, SEED);
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::ReFungible(2048, 3));
+        let item_id = create_item_helper::<T>(caller.clone(), collection_id, 2048);
+        split_item_helper::<T>(caller.clone(), collection_id, item_id, o);
+        Module::<T>::approve(RawOrigin::Signed(caller.clone()).into(), spender.clone(), collection_id, item_id, 1)?;
+    }: _(RawOrigin::Signed(spender), caller.clone(), recipient.clone(), collection_id, item_id, 1, vec![])
+    verify {
+        assert_eq!(<Balance<T>>::get(collection_id, recipient), 1);
+    }
+
+    set_offchain_schema {
+        let b in ...;
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+        let schema = vec![0u8; b as usize];
+    }: _(RawOrigin::Signed(caller), collection_id, schema.clone())
+    verify {
+        assert_eq!(<Collection<T>>::get(collection_id).offchain_schema, schema);
+    }
+
+    set_public_access_mode {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+    }: _(RawOrigin::Signed(caller), collection_id, AccessMode::WhiteList)
+    verify {
+        assert_eq!(<Collection<T>>::get(collection_id).access, AccessMode::WhiteList);
+    }
+
+    add_to_white_list {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let address: T::AccountId = account("address", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+    }: _(RawOrigin::Signed(caller), collection_id, address.clone())
+    verify {
+        assert!(<WhiteList<T>>::get(collection_id, address));
+    }
+
+    remove_from_white_list {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let address: T::AccountId = account("address", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+        Module::<T>::add_to_white_list(RawOrigin::Signed(caller.clone()).into(), collection_id, address.clone())?;
+    }: _(RawOrigin::Signed(caller), collection_id, address.clone())
+    verify {
+        assert!(!<WhiteList<T>>::get(collection_id, address));
+    }
+
+    set_mint_permission {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+    }: _(RawOrigin::Signed(caller), collection_id, true, 10)
+    verify {
+        assert!(<Collection<T>>::get(collection_id).mint_mode);
+    }
+
+    set_contract_sponsor {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let contract: T::AccountId = account("contract", 0, SEED);
+        let sponsor: T::AccountId = account("sponsor", 0, SEED);
+        <ContractOwner<T>>::insert(contract.clone(), caller.clone());
+    }: _(RawOrigin::Signed(caller), contract.clone(), sponsor.clone())
+    verify {
+        assert_eq!(<UnconfirmedContractSponsor<T>>::get(contract), sponsor);
+    }
+
+    confirm_contract_sponsorship {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let contract: T::AccountId = account("contract", 0, SEED);
+        let sponsor: T::AccountId = account("sponsor", 0, SEED);
+        <ContractOwner<T>>::insert(contract.clone(), caller.clone());
+        Module::<T>::set_contract_sponsor(RawOrigin::Signed(caller).into(), contract.clone(), sponsor.clone())?;
+    }: _(RawOrigin::Signed(sponsor.clone()), contract.clone())
+    verify {
+        assert_eq!(<ContractSponsor<T>>::get(contract), sponsor);
+    }
+
+    remove_contract_sponsor {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let contract: T::AccountId = account("contract", 0, SEED);
+        let sponsor: T::AccountId = account("sponsor", 0, SEED);
+        <ContractOwner<T>>::insert(contract.clone(), caller.clone());
+        Module::<T>::set_contract_sponsor(RawOrigin::Signed(caller.clone()).into(), contract.clone(), sponsor.clone())?;
+        Module::<T>::confirm_contract_sponsorship(RawOrigin::Signed(sponsor).into(), contract.clone())?;
+    }: _(RawOrigin::Signed(caller), contract.clone())
+    verify {
+        assert!(!<ContractSponsor<T>>::contains_key(contract));
+    }
+
+    set_contract_owner {
+        let contract: T::AccountId = account("contract", 0, SEED);
+        let owner: T::AccountId = account("owner", 0, SEED);
+    }: _(RawOrigin::Root, contract.clone(), owner.clone())
+    verify {
+        assert_eq!(<ContractOwner<T>>::get(contract), owner);
+    }
+
+    set_contract_sponsorship_limits {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let contract: T::AccountId = account("contract", 0, SEED);
+        <ContractOwner<T>>::insert(contract.clone(), caller.clone());
+        let limits = ContractSponsorshipLimitsType {
+            call_timeout: 10,
+            block_fee_limit: 1_000_000,
+            max_tx_fee: 1_000,
+        };
+    }: _(RawOrigin::Signed(caller), contract.clone(), limits.clone())
+    verify {
+        assert_eq!(<ContractSponsorshipLimits<T>>::get(contract), limits);
+    }
+
+    set_sponsorship_limits {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+        let limits = SponsorshipLimitsType {
+            create_item_timeout: 10,
+            transfer_timeout: 10,
+            block_fee_limit: 1_000_000_000,
+            max_tx_fee: 1_000_000,
+        };
+    }: _(RawOrigin::Signed(caller), collection_id, limits.clone())
+    verify {
+        assert_eq!(<SponsorshipLimits>::get(collection_id), limits);
+    }
+
+    select_fee_model {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+    }: _(RawOrigin::Signed(caller), collection_id, FeeModel::PayAsYouGo)
+    verify {
+        assert_eq!(<Collection<T>>::get(collection_id).fee_model, FeeModel::PayAsYouGo);
+    }
+
+    set_collection_name {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+        let name = vec![0x41u16; MAX_COLLECTION_NAME_LENGTH - 1];
+    }: _(RawOrigin::Signed(caller), collection_id, name)
+    verify {
+        assert_eq!(<Collection<T>>::get(collection_id).name.len(), MAX_COLLECTION_NAME_LENGTH);
+    }
+
+    set_collection_description {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+        let description = vec![0x41u16; MAX_COLLECTION_DESCRIPTION_LENGTH - 1];
+    }: _(RawOrigin::Signed(caller), collection_id, description)
+    verify {
+        assert_eq!(<Collection<T>>::get(collection_id).description.len(), MAX_COLLECTION_DESCRIPTION_LENGTH);
+    }
+
+    set_token_prefix {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+        let prefix = vec![0x41u8; MAX_TOKEN_PREFIX_LENGTH - 1];
+    }: _(RawOrigin::Signed(caller), collection_id, prefix)
+    verify {
+        assert_eq!(<Collection<T>>::get(collection_id).token_prefix.len(), MAX_TOKEN_PREFIX_LENGTH);
+    }
+
+    set_variable_data_policy {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+    }: _(RawOrigin::Signed(caller), collection_id, 2048, VariableDataMode::ItemOwnerAndAdmin)
+    verify {
+        assert_eq!(<Collection<T>>::get(collection_id).variable_data_size, 2048);
+    }
+
+    set_variable_data {
+        let b in ...;
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+        let item_id = create_item_helper::<T>(caller.clone(), collection_id, 2048);
+        Module::<T>::set_variable_data_policy(RawOrigin::Signed(caller.clone()).into(), collection_id, 2048, VariableDataMode::ItemOwnerAndAdmin)?;
+        let data = vec![0u8; b as usize];
+    }: _(RawOrigin::Signed(caller), collection_id, item_id, data.clone())
+    verify {
+        assert_eq!(<NftItemList<T>>::get(collection_id, item_id).variable_data, data);
+    }
+
+    set_sponsored_calls {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+    }: _(RawOrigin::Signed(caller), collection_id, SPONSOR_TRANSFER)
+    verify {
+        assert_eq!(<Collection<T>>::get(collection_id).unsponsored_calls, SPONSOR_ALL_CALLS & !SPONSOR_TRANSFER);
+    }
+
+    deposit_to_collection {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+        let value: BalanceOf<T> = 1_000_000u32.into();
+    }: _(RawOrigin::Signed(caller), collection_id, value)
+    verify {
+        assert_eq!(T::Currency::free_balance(&Module::<T>::collection_deposit_account(collection_id)), value);
+    }
+
+    withdraw_from_collection {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+        let value: BalanceOf<T> = 1_000_000u32.into();
+        Module::<T>::deposit_to_collection(RawOrigin::Signed(caller.clone()).into(), collection_id, value)?;
+    }: _(RawOrigin::Signed(caller), collection_id, value)
+    verify {
+        assert!(T::Currency::free_balance(&Module::<T>::collection_deposit_account(collection_id)).is_zero());
+    }
+
+    buy_resources {
+        let caller: T::AccountId = account("caller", 0, SEED);
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+        let collection_id = create_collection_helper::<T>(caller.clone(), CollectionMode::NFT(2048));
+    }: _(RawOrigin::Signed(caller), collection_id, 1000)
+    verify {
+        assert_eq!(ResourceQuota::get(collection_id), 1000);
+    }
+
+    set_create_collection_fee {
+        let fee: BalanceOf<T> = 1_000_000u32.into();
+    }: _(RawOrigin::Root, Some(fee))
+    verify {
+        assert_eq!(Module::<T>::create_collection_fee(), fee);
+    }
+
+    set_call_flat_fee {
+        let fee: BalanceOf<T> = 1_000_000u32.into();
+    }: _(RawOrigin::Root, b"safe_transfer_from".to_vec(), Some(fee))
+    verify {
+        assert_eq!(Module::<T>::call_flat_fee(b"safe_transfer_from".to_vec()), Some(fee));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{new_test_ext, Test};
+    use frame_support::assert_ok;
+
+    #[test]
+    fn test_benchmarks() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_create_collection::<Test>());
+            assert_ok!(test_benchmark_destroy_collection::<Test>());
+            assert_ok!(test_benchmark_change_collection_owner::<Test>());
+            assert_ok!(test_benchmark_add_collection_admin::<Test>());
+            assert_ok!(test_benchmark_remove_collection_admin::<Test>());
+            assert_ok!(test_benchmark_set_collection_sponsor::<Test>());
+            assert_ok!(test_benchmark_confirm_sponsorship::<Test>());
+            assert_ok!(test_benchmark_remove_collection_sponsor::<Test>());
+            assert_ok!(test_benchmark_create_item::<Test>());
+            assert_ok!(test_benchmark_create_multiple_items::<Test>());
+            assert_ok!(test_benchmark_burn_item::<Test>());
+            assert_ok!(test_benchmark_transfer::<Test>());
+            assert_ok!(test_benchmark_approve::<Test>());
+            assert_ok!(test_benchmark_set_approval_for_all::<Test>());
+            assert_ok!(test_benchmark_revoke_approval::<Test>());
+            assert_ok!(test_benchmark_clear_approvals::<Test>());
+            assert_ok!(test_benchmark_transfer_from::<Test>());
+            assert_ok!(test_benchmark_safe_transfer_from::<Test>());
+            assert_ok!(test_benchmark_set_offchain_schema::<Test>());
+            assert_ok!(test_benchmark_set_public_access_mode::<Test>());
+            assert_ok!(test_benchmark_add_to_white_list::<Test>());
+            assert_ok!(test_benchmark_remove_from_white_list::<Test>());
+            assert_ok!(test_benchmark_set_mint_permission::<Test>());
+            assert_ok!(test_benchmark_set_contract_sponsor::<Test>());
+            assert_ok!(test_benchmark_confirm_contract_sponsorship::<Test>());
+            assert_ok!(test_benchmark_remove_contract_sponsor::<Test>());
+            assert_ok!(test_benchmark_set_contract_owner::<Test>());
+            assert_ok!(test_benchmark_set_contract_sponsorship_limits::<Test>());
+            assert_ok!(test_benchmark_set_sponsorship_limits::<Test>());
+            assert_ok!(test_benchmark_select_fee_model::<Test>());
+            assert_ok!(test_benchmark_set_sponsored_calls::<Test>());
+            assert_ok!(test_benchmark_set_collection_name::<Test>());
+            assert_ok!(test_benchmark_set_collection_description::<Test>());
+            assert_ok!(test_benchmark_set_token_prefix::<Test>());
+            assert_ok!(test_benchmark_set_variable_data_policy::<Test>());
+            assert_ok!(test_benchmark_set_variable_data::<Test>());
+            assert_ok!(test_benchmark_deposit_to_collection::<Test>());
+            assert_ok!(test_benchmark_withdraw_from_collection::<Test>());
+            assert_ok!(test_benchmark_buy_resources::<Test>());
+            assert_ok!(test_benchmark_set_create_collection_fee::<Test>());
+            assert_ok!(test_benchmark_set_call_flat_fee::<Test>());
+        });
+    }
+}
@@ -0,0 +1,231 @@
+//! Default weights of the nft pallet.
+//!
+//! These are not benchmark results. Execution times are rough estimates rounded
+//! up to one significant digit, database reads and writes are counted from the
+//! code. Replace this file with the output of the benchmark subcommand on
+//! reference hardware, see Benchmarking in README.
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight as DbWeight}};
+
+impl crate::WeightInfo for () {
+    fn create_collection() -> Weight {
+        (50_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn destroy_collection(i: u32) -> Weight {
+        (80_000_000 as Weight)
+            .saturating_add((30_000_000 as Weight).saturating_mul(i as Weight))
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(i as Weight)))
+            .saturating_add(DbWeight::get().writes(15 as Weight))
+            .saturating_add(DbWeight::get().writes((4 as Weight).saturating_mul(i as Weight)))
+    }
+    fn change_collection_owner() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn add_collection_admin(a: u32) -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add((500_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn remove_collection_admin(a: u32) -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add((400_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_collection_sponsor() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn confirm_sponsorship() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn remove_collection_sponsor() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn create_item(b: u32) -> Weight {
+        (70_000_000 as Weight)
+            .saturating_add((4_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(DbWeight::get().reads(7 as Weight))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
+    }
+    fn create_multiple_items(n: u32, s: u32) -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add((30_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add((4_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+            .saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+    }
+    fn burn_item() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(6 as Weight))
+    }
+    fn transfer(o: u32) -> Weight {
+        (70_000_000 as Weight)
+            .saturating_add((2_000_000 as Weight).saturating_mul(o as Weight))
+            .saturating_add(DbWeight::get().reads(7 as Weight))
+            .saturating_add(DbWeight::get().writes(8 as Weight))
+    }
+    fn approve(l: u32) -> Weight {
+        (50_000_000 as Weight)
+            .saturating_add((100_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(DbWeight::get().reads(7 as Weight))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
+    }
+    fn revoke_approval(l: u32) -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add((100_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(DbWeight::get().reads(8 as Weight))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
+    }
+    fn clear_approvals(l: u32) -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add((2_000_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(DbWeight::get().reads(69 as Weight))
+            .saturating_add(DbWeight::get().writes(64 as Weight))
+    }
+    fn set_approval_for_all() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn transfer_from(o: u32) -> Weight {
+        (80_000_000 as Weight)
+            .saturating_add((2_000_000 as Weight).saturating_mul(o as Weight))
+            .saturating_add(DbWeight::get().reads(12 as Weight))
+            .saturating_add(DbWeight::get().writes(9 as Weight))
+    }
+    fn safe_transfer_from(o: u32) -> Weight {
+        (80_000_000 as Weight)
+            .saturating_add((2_000_000 as Weight).saturating_mul(o as Weight))
+            .saturating_add(DbWeight::get().reads(21 as Weight))
+            .saturating_add(DbWeight::get().writes(9 as Weight))
+    }
+    fn set_offchain_schema(b: u32) -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add((3_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_public_access_mode() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn add_to_white_list() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn remove_from_white_list() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_mint_permission() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_contract_sponsor() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn confirm_contract_sponsorship() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn remove_contract_sponsor() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn set_contract_owner() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_contract_sponsorship_limits() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_sponsorship_limits() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn select_fee_model() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn deposit_to_collection() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn withdraw_from_collection() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn buy_resources() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+    fn set_create_collection_fee() -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_call_flat_fee() -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_sponsored_calls() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_collection_name() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_collection_description() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_token_prefix() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_variable_data_policy() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+    fn set_variable_data(b: u32) -> Weight {
+        (50_000_000 as Weight)
+            .saturating_add((3_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+}
@@ -3,31 +3,35 @@
 /// For more guidance on Substrate FRAME, see the example pallet
 /// https://github.com/paritytech/substrate/blob/master/frame/example/src/lib.rs
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, FullCodec};
 pub use frame_support::{
-    decl_event, decl_module, decl_storage,
+    decl_event, decl_module, decl_storage, decl_error,
     construct_runtime, parameter_types,
-    traits::{Currency, Get, ExistenceRequirement, KeyOwnerProofSystem, OnUnbalanced, Randomness, WithdrawReason, Imbalance},
+    traits::{Currency, Get, ExistenceRequirement, KeyOwnerProofSystem, OnUnbalanced, Randomness, WithdrawReason, WithdrawReasons, Imbalance, FindAuthor},
     weights::{
         DispatchInfo, PostDispatchInfo, constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
         IdentityFee, Weight, WeightToFeePolynomial, GetDispatchInfo, Pays,
     },
     StorageValue,
-    dispatch::DispatchResult, 
+    storage::{IterableStorageDoubleMap, with_transaction, TransactionOutcome},
+    dispatch::{DispatchResult, GetCallName},
     IsSubType,
     ensure
 };
 
-use frame_system::{self as system, ensure_signed};
+use frame_system::{self as system, ensure_signed, ensure_root};
+use contracts::ContractAddressFor;
 use sp_runtime::sp_std::prelude::Vec;
 use sp_std::prelude::*;
+use sp_std::marker::PhantomData;
+use sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
 use sp_runtime::{
-	FixedU128, FixedPointOperand, 
+	FixedU128, FixedPointOperand, ModuleId, Perbill, DispatchError,
 	transaction_validity::{
 		TransactionPriority, ValidTransaction, InvalidTransaction, TransactionValidityError, TransactionValidity
 	},
 	traits::{
-        Saturating, Dispatchable, DispatchInfoOf, PostDispatchInfoOf, SignedExtension, Zero, SaturatedConversion,
+        Saturating, Dispatchable, StaticLookup, AccountIdConversion, DispatchInfoOf, PostDispatchInfoOf, SignedExtension, Zero, SaturatedConversion, CheckedMul,
 	},
 };
 
@@ -37,14 +41,60 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+mod default_weights;
+mod migration;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+/// Maximum number of admins of a single collection.
+pub const MAX_COLLECTION_ADMINS: u32 = 16;
+/// Maximum number of owners of a single re-fungible item.
+pub const MAX_REFUNGIBLE_OWNERS: u32 = 64;
+/// Maximum number of entries of destroyed collections (item owners, approvals,
+/// account entries) removed per call or block.
+pub const COLLECTION_CLEANUP_ITEMS_LIMIT: u32 = 64;
+/// Maximum number of accounts an owner can approve to transfer a single item.
+pub const MAX_APPROVED_PER_OWNER: u32 = 16;
+/// Maximum number of accounts approved to transfer a single item by all of
+/// its owners.
+pub const MAX_APPROVED_PER_ITEM: u32 = MAX_APPROVED_PER_OWNER * MAX_REFUNGIBLE_OWNERS;
+/// Maximum number of operators of a single account in a collection.
+pub const MAX_OPERATORS_PER_ACCOUNT: u32 = 16;
+/// Maximum number of items created or transferred by one batch call.
+pub const MAX_ITEMS_PER_BATCH: u32 = 1000;
+/// Maximum length of collection name in UTF-16 code units, including null escape char.
+pub const MAX_COLLECTION_NAME_LENGTH: usize = 64;
+/// Maximum length of collection description in UTF-16 code units, including null escape char.
+pub const MAX_COLLECTION_DESCRIPTION_LENGTH: usize = 256;
+/// Maximum length of token prefix in bytes, including null escape char.
+pub const MAX_TOKEN_PREFIX_LENGTH: usize = 16;
+/// Maximum size of variable data of a single item.
+pub const MAX_VARIABLE_DATA_SIZE: u32 = 2048;
+/// Selector of the `on_nft_received` message of recipient contracts
+/// (first 4 bytes of blake2b-256 of the message name). The contract accepts
+/// an item by returning these same 4 bytes.
+pub const ON_NFT_RECEIVED_SELECTOR: [u8; 4] = [0x91, 0xb2, 0xa4, 0x98];
+
+// Bits of the mask of calls paid by collection, see CollectionType::unsponsored_calls
+pub const SPONSOR_CREATE_ITEM: u32 = 1;
+pub const SPONSOR_BURN_ITEM: u32 = 1 << 1;
+pub const SPONSOR_TRANSFER: u32 = 1 << 2;
+pub const SPONSOR_APPROVE: u32 = 1 << 3;
+pub const SPONSOR_TRANSFER_FROM: u32 = 1 << 4;
+pub const SPONSOR_SAFE_TRANSFER_FROM: u32 = 1 << 5;
+pub const SPONSOR_SET_VARIABLE_DATA: u32 = 1 << 6;
+pub const SPONSOR_ALL_CALLS: u32 = SPONSOR_CREATE_ITEM | SPONSOR_BURN_ITEM | SPONSOR_TRANSFER |
+    SPONSOR_APPROVE | SPONSOR_TRANSFER_FROM | SPONSOR_SAFE_TRANSFER_FROM | SPONSOR_SET_VARIABLE_DATA;
+
 #[derive(Encode, Decode, Debug, Eq, Clone, PartialEq)]
 pub enum CollectionMode {
     Invalid,
-    // custom data size
+    // const data size
     NFT(u32),
     // decimal points
     Fungible(u32),
-    // custom data size and decimal points
+    // const data size and decimal points
 	ReFungible(u32, u32),
 }
 
@@ -59,13 +109,32 @@ impl Into<u8> for CollectionMode {
     }
 }
 
-#[derive(Encode, Decode, Debug, Clone, PartialEq)]
+#[derive(Encode, Decode, Debug, Eq, Clone, PartialEq)]
 pub enum AccessMode {
     Normal,
 	WhiteList,
 }
 impl Default for AccessMode { fn default() -> Self { Self::Normal } }
 
+// Who pays fees of collection transactions, see doc/economic_model.md
+#[derive(Encode, Decode, Debug, Eq, Clone, PartialEq)]
+pub enum FeeModel {
+    UserPaid,
+    PayAsYouGo,
+    PrepaidPlan,
+    ResourcePurchase,
+}
+impl Default for FeeModel { fn default() -> Self { Self::UserPaid } }
+
+// Who can change variable data of collection items
+#[derive(Encode, Decode, Debug, Eq, Clone, PartialEq)]
+pub enum VariableDataMode {
+    ItemOwner,
+    Admin,
+    ItemOwnerAndAdmin,
+}
+impl Default for VariableDataMode { fn default() -> Self { Self::ItemOwner } }
+
 impl Default for CollectionMode { fn default() -> Self { Self::Invalid } }
 
 #[derive(Encode, Decode, Default, Clone, PartialEq)]
@@ -85,10 +154,41 @@ pub struct CollectionType<AccountId> {
     pub name: Vec<u16>,        // 64 include null escape char
     pub description: Vec<u16>, // 256 include null escape char
     pub token_prefix: Vec<u8>, // 16 include null escape char
-    pub custom_data_size: u32,
+    pub custom_data_size: u32, // size of item const data, set at creation
     pub offchain_schema: Vec<u8>,
     pub sponsor: AccountId,    // Who pays fees. If set to default address, the fees are applied to the transaction sender
     pub unconfirmed_sponsor: AccountId, // Sponsor address that has not yet confirmed sponsorship
+    pub mint_mode: bool,       // If set, not only owner and admins can create items (see create_item)
+    pub per_account_mint_limit: u32, // Items a non-admin account can create, 0 means no limit
+    pub fee_model: FeeModel,
+    pub unsponsored_calls: u32, // Mask of SPONSOR_* bits, calls paid by their senders regardless of fee model
+    pub variable_data_size: u32, // size of item data that can be changed later, 0 by default
+    pub variable_data_mode: VariableDataMode,
+}
+
+// Limits of collection sponsorship, zero value means no limit
+#[derive(Encode, Decode, Default, Debug, Eq, Clone, PartialEq)]
+pub struct SponsorshipLimitsType {
+    pub create_item_timeout: u32, // blocks between sponsored create_item calls of the same account
+    pub transfer_timeout: u32,    // blocks between sponsored transfers (and other calls) of the same item
+    pub block_fee_limit: u128,    // total fee sponsored in one block
+    pub max_tx_fee: u128,         // maximum fee of a sponsored transaction
+}
+
+// Limits of contract sponsorship, zero value means no limit
+#[derive(Encode, Decode, Default, Debug, Eq, Clone, PartialEq)]
+pub struct ContractSponsorshipLimitsType {
+    pub call_timeout: u32,     // blocks between sponsored calls of the same account
+    pub block_fee_limit: u128, // total fee sponsored in one block
+    pub max_tx_fee: u128,      // maximum fee of a sponsored transaction
+}
+
+#[derive(Encode, Decode, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct DestroyingCollectionType {
+    pub mode: CollectionMode,
+    pub next_item_id: u64,   // first item that is not removed yet
+    pub last_item_id: u64,   // last created item, taken from ItemListIndex
 }
 
 #[derive(Encode, Decode, Default, Clone, PartialEq)]
@@ -103,7 +203,8 @@ pub struct CollectionAdminsType<AccountId> {
 pub struct NftItemType<AccountId> {
     pub collection: u64,
     pub owner: AccountId,
-    pub data: Vec<u8>,
+    pub const_data: Vec<u8>,
+    pub variable_data: Vec<u8>,
 }
 
 #[derive(Encode, Decode, Default, Clone, PartialEq)]
@@ -119,28 +220,130 @@ pub struct FungibleItemType<AccountId> {
 pub struct ReFungibleItemType<AccountId> {
     pub collection: u64,
     pub owner: Vec<Ownership<AccountId>>,
-    pub data: Vec<u8>,
+    pub const_data: Vec<u8>,
+    pub variable_data: Vec<u8>,
+}
+
+pub trait WeightInfo {
+    fn create_collection() -> Weight;
+    fn destroy_collection(i: u32) -> Weight;
+    fn change_collection_owner() -> Weight;
+    fn add_collection_admin(a: u32) -> Weight;
+    fn remove_collection_admin(a: u32) -> Weight;
+    fn set_collection_sponsor() -> Weight;
+    fn confirm_sponsorship() -> Weight;
+    fn remove_collection_sponsor() -> Weight;
+    fn create_item(b: u32) -> Weight;
+    fn create_multiple_items(n: u32, s: u32) -> Weight;
+    fn burn_item() -> Weight;
+    fn transfer(o: u32) -> Weight;
+    fn approve(l: u32) -> Weight;
+    fn revoke_approval(l: u32) -> Weight;
+    fn clear_approvals(l: u32) -> Weight;
+    fn set_approval_for_all() -> Weight;
+    fn transfer_from(o: u32) -> Weight;
+    fn safe_transfer_from(o: u32) -> Weight;
+    fn set_offchain_schema(b: u32) -> Weight;
+    fn set_public_access_mode() -> Weight;
+    fn add_to_white_list() -> Weight;
+    fn remove_from_white_list() -> Weight;
+    fn set_mint_permission() -> Weight;
+    fn set_contract_sponsor() -> Weight;
+    fn confirm_contract_sponsorship() -> Weight;
+    fn remove_contract_sponsor() -> Weight;
+    fn set_contract_owner() -> Weight;
+    fn set_contract_sponsorship_limits() -> Weight;
+    fn set_sponsorship_limits() -> Weight;
+    fn select_fee_model() -> Weight;
+    fn deposit_to_collection() -> Weight;
+    fn withdraw_from_collection() -> Weight;
+    fn buy_resources() -> Weight;
+    fn set_create_collection_fee() -> Weight;
+    fn set_call_flat_fee() -> Weight;
+    fn set_sponsored_calls() -> Weight;
+    fn set_collection_name() -> Weight;
+    fn set_collection_description() -> Weight;
+    fn set_token_prefix() -> Weight;
+    fn set_variable_data_policy() -> Weight;
+    fn set_variable_data(b: u32) -> Weight;
+}
+
+/// Contracts of the runtime, as seen by the pallet.
+pub trait ContractsHandler<AccountId> {
+    /// Whether the account is a contract.
+    fn is_contract(account: &AccountId) -> bool;
+
+    /// Call the contract with `input`, returns output of a successful call.
+    fn call(origin: AccountId, contract: AccountId, gas_limit: Weight, input: Vec<u8>) -> Option<Vec<u8>>;
+}
+
+/// ContractsHandler of a runtime with the contracts pallet.
+pub struct ContractsAdapter<T>(PhantomData<T>);
+
+impl<T: contracts::Trait> ContractsHandler<T::AccountId> for ContractsAdapter<T> {
+    fn is_contract(account: &T::AccountId) -> bool {
+        <contracts::ContractInfoOf<T>>::contains_key(account)
+    }
+
+    fn call(origin: T::AccountId, contract: T::AccountId, gas_limit: Weight, input: Vec<u8>) -> Option<Vec<u8>> {
+        <contracts::Module<T>>::bare_call(origin, contract, Zero::zero(), gas_limit, input)
+            .ok()
+            .filter(|result| result.is_success())
+            .map(|result| result.data)
+    }
 }
 
-pub trait Trait: system::Trait {
+pub trait Trait: system::Trait + transaction_payment::Trait {
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
+    /// Id of the pallet, collection deposit accounts are derived from it.
+    type ModuleId: Get<ModuleId>;
+
+    /// Price of one transaction bought in Resource Purchase fee model.
+    type ResourceTxPrice: Get<BalanceOf<Self>>;
+
+    /// Handler for the funds paid for resources.
+    type OnResourcePayment: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+    /// Default fee of collection creation, charged in addition to the weight fee.
+    type CollectionCreationFee: Get<BalanceOf<Self>>;
+
+    /// Gas limit of the `on_nft_received` call to a recipient contract.
+    type OnNftReceivedGasLimit: Get<Weight>;
+
+    /// Contracts of the runtime, their deployers become contract owners and
+    /// recipient contracts of `safe_transfer_from` are asked to accept items.
+    type Contracts: ContractsHandler<Self::AccountId>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
 }
 
 decl_storage! {
     trait Store for Module<T: Trait> as Nft {
 
+        // Version of storage layout, see migration.rs
+        StorageVersion build(|_| migration::STORAGE_VERSION): u32;
+
         // Private members
         NextCollectionID: u64;
         ItemListIndex: map hasher(blake2_128_concat) u64 => u64;
 
         pub Collection get(fn collection): map hasher(identity) u64 => CollectionType<T::AccountId>;
         pub AdminList get(fn admin_list_collection): map hasher(identity) u64 => Vec<T::AccountId>;
-        pub WhiteList get(fn white_list): map hasher(identity) u64 => Vec<T::AccountId>;
+        pub WhiteList get(fn white_list): double_map hasher(identity) u64, hasher(blake2_128_concat) T::AccountId => bool;
 
         // Balance owner per collection map
         pub Balance get(fn balance_count): double_map hasher(blake2_128_concat) u64, hasher(blake2_128_concat) T::AccountId => u64;
         pub ApprovedList get(fn approved): double_map hasher(blake2_128_concat) u64, hasher(blake2_128_concat) u64 => Vec<T::AccountId>;
+        // Amount of an item the spender can transfer on behalf of the owner, see allowance
+        Allowances: double_map hasher(blake2_128_concat) (u64, u64), hasher(blake2_128_concat) (T::AccountId, T::AccountId) => u64;
+        // Spenders approved by an owner of an item, ApprovedList is their union over owners
+        OwnerApprovedList: double_map hasher(blake2_128_concat) (u64, u64), hasher(blake2_128_concat) T::AccountId => Vec<T::AccountId>;
+        // Number of owners of an item that approved the spender
+        ApprovalOwnerCount: double_map hasher(blake2_128_concat) (u64, u64), hasher(blake2_128_concat) T::AccountId => u32;
+        // Operators approved to transfer all items of an account in a collection
+        pub OperatorApprovals get(fn operator_approvals): double_map hasher(blake2_128_concat) u64, hasher(blake2_128_concat) T::AccountId => Vec<T::AccountId>;
 
         // Item collections
         pub NftItemList get(fn nft_item_id): double_map hasher(blake2_128_concat) u64, hasher(blake2_128_concat) u64 => NftItemType<T::AccountId>;
@@ -149,9 +352,102 @@ decl_storage! {
 
         pub AddressTokens get(fn address_tokens): double_map hasher(blake2_128_concat) u64, hasher(blake2_128_concat) T::AccountId => Vec<u64>;
 
+        // Items created by non-admin accounts per collection
+        pub AccountMinted get(fn account_minted): double_map hasher(identity) u64, hasher(blake2_128_concat) T::AccountId => u32;
+
+        // Collections whose items are being removed, in teardown order:
+        // queue positions from DestroyQueueHead up to DestroyQueueTail (exclusive)
+        DestroyQueueHead: u64;
+        DestroyQueueTail: u64;
+        pub DestroyQueue get(fn destroy_queue): map hasher(identity) u64 => Option<u64>;
+        pub DestroyingCollection get(fn destroying_collection): map hasher(identity) u64 => DestroyingCollectionType;
+
         // Sponsorship
+        pub SponsorshipLimits get(fn sponsorship_limits): map hasher(identity) u64 => SponsorshipLimitsType;
+        pub CreateItemSponsorBasket get(fn create_item_sponsor_basket): double_map hasher(identity) u64, hasher(blake2_128_concat) T::AccountId => Option<T::BlockNumber>;
+        pub TransferSponsorBasket get(fn transfer_sponsor_basket): double_map hasher(identity) u64, hasher(identity) (u64, u32) => Option<T::BlockNumber>;
+        pub SponsoredFees get(fn sponsored_fees): map hasher(identity) u64 => (T::BlockNumber, u128);
+        pub ResourceQuota get(fn resource_quota): map hasher(identity) u64 => u64;
+
+        // Fees set by governance
+        CreateCollectionFee: Option<BalanceOf<T>>;
+        pub CallFlatFee get(fn call_flat_fee): map hasher(blake2_128_concat) Vec<u8> => Option<BalanceOf<T>>;
+        pub ContractOwner get(fn contract_owner): map hasher(identity) T::AccountId => T::AccountId;
         pub ContractSponsor get(fn contract_sponsor): map hasher(identity) T::AccountId => T::AccountId;
         pub UnconfirmedContractSponsor get(fn unconfirmed_contract_sponsor): map hasher(identity) T::AccountId => T::AccountId;
+        pub ContractSponsorshipLimits get(fn contract_sponsorship_limits): map hasher(blake2_128_concat) T::AccountId => ContractSponsorshipLimitsType;
+        pub ContractSponsorBasket get(fn contract_sponsor_basket): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => Option<T::BlockNumber>;
+        pub ContractSponsoredFees get(fn contract_sponsored_fees): map hasher(blake2_128_concat) T::AccountId => (T::BlockNumber, u128);
+    }
+}
+
+decl_error! {
+    /// Error for non-fungible-token module.
+    pub enum Error for Module<T: Trait> {
+        /// Total collections bound exceeded.
+        TotalCollectionsLimitExceeded,
+        /// Decimal_points parameter must be lower than 4.
+        CollectionDecimalPointLimitExceeded,
+        /// Collection name can not be longer than 63 char.
+        CollectionNameLimitExceeded,
+        /// Collection description can not be longer than 255 char.
+        CollectionDescriptionLimitExceeded,
+        /// Token prefix can not be longer than 15 char.
+        CollectionTokenPrefixLimitExceeded,
+        /// Collection name is not a valid UTF-16 string.
+        CollectionNameInvalid,
+        /// Collection description is not a valid UTF-16 string.
+        CollectionDescriptionInvalid,
+        /// This collection does not exist.
+        CollectionNotFound,
+        /// Item not exists.
+        ItemNotFound,
+        /// Arithmetic calculation overflow.
+        NumOverflow,
+        /// Account already has admin role.
+        AlreadyAdmin,
+        /// You do not have permissions to modify this collection or item.
+        NoPermission,
+        /// This address is not set as sponsor, use setCollectionSponsor first.
+        ConfirmUnsetSponsorFail,
+        /// Size of item is too large.
+        DataTooLarge,
+        /// Collection mode does not support this operation.
+        UnexpectedCollectionType,
+        /// Item balance not enough.
+        InsufficientFraction,
+        /// Collection admins limit exceeded.
+        CollectionAdminsLimitExceeded,
+        /// Re-fungible item owners limit exceeded.
+        RefungibleOwnersLimitExceeded,
+        /// Address is not in white list.
+        AddressNotInWhiteList,
+        /// Account can not create more items in this collection.
+        AccountMintLimitExceeded,
+        /// Pallet has no call with this name.
+        UnknownCall,
+        /// Batch has no items.
+        EmptyBatch,
+        /// Too many items in one batch.
+        ItemsPerBatchLimitExceeded,
+        /// Item is transferred more than once in one batch.
+        BatchItemDuplicated,
+        /// Owner approved list limit of the item exceeded.
+        ApprovedListLimitExceeded,
+        /// Address is not approved to transfer the item.
+        AddressNotApproved,
+        /// Account operators limit exceeded.
+        OperatorsLimitExceeded,
+        /// Transferred value is larger than the allowance.
+        AllowanceExceeded,
+        /// Recipient contract did not accept the item.
+        ReceiverRejectedItem,
+        /// Transferred value of a fungible or re-fungible item is zero.
+        ZeroTransferValue,
+        /// Variable data size of collection items is too large.
+        VariableDataSizeLimitExceeded,
+        /// Sponsored calls mask has bits of unknown calls.
+        UnknownSponsoredCall,
     }
 }
 
@@ -159,23 +455,120 @@ decl_event!(
     pub enum Event<T>
     where
         AccountId = <T as system::Trait>::AccountId,
+        Balance = BalanceOf<T>,
     {
+        /// New collection was created: collection id, collection mode, owner.
         Created(u64, u8, AccountId),
+        /// New item was created: collection id, item id.
         ItemCreated(u64, u64),
+        /// Items with consecutive ids were created: collection id, first item id, last item id.
+        ItemsCreated(u64, u64, u64),
+        /// Item was burned: collection id, item id.
         ItemDestroyed(u64, u64),
+        /// Item was transferred: collection id, item id, sender, recipient, amount.
+        Transfer(u64, u64, AccountId, AccountId, u64),
+        /// Allowance of an item was set: collection id, item id, owner, spender, amount.
+        Approved(u64, u64, AccountId, AccountId, u64),
+        /// Approval was revoked: collection id, item id, owner, account that is no longer approved.
+        ApprovalRevoked(u64, u64, AccountId, AccountId),
+        /// All approvals of an item were removed: collection id, item id.
+        ApprovalsCleared(u64, u64),
+        /// Operator approval was changed: collection id, owner, operator, approved.
+        ApprovalForAll(u64, AccountId, AccountId, bool),
+        /// Collection admin was added: collection id, admin.
+        AdminAdded(u64, AccountId),
+        /// Collection admin was removed: collection id, admin.
+        AdminRemoved(u64, AccountId),
+        /// Collection owner was changed: collection id, previous owner, new owner.
+        OwnerChanged(u64, AccountId, AccountId),
+        /// Collection sponsor was proposed: collection id, unconfirmed sponsor.
+        SponsorSet(u64, AccountId),
+        /// Collection sponsorship was confirmed: collection id, sponsor.
+        SponsorConfirmed(u64, AccountId),
+        /// Collection sponsor was removed: collection id.
+        SponsorRemoved(u64),
+        /// Collection offchain schema was updated: collection id, schema.
+        OffchainSchemaSet(u64, Vec<u8>),
+        /// Collection name was changed: collection id, name.
+        CollectionNameSet(u64, Vec<u16>),
+        /// Collection description was changed: collection id, description.
+        CollectionDescriptionSet(u64, Vec<u16>),
+        /// Collection token prefix was changed: collection id, token prefix.
+        TokenPrefixSet(u64, Vec<u8>),
+        /// Collection variable data policy was changed: collection id, variable data size, mode.
+        VariableDataPolicySet(u64, u32, VariableDataMode),
+        /// Item variable data was changed: collection id, item id.
+        VariableDataSet(u64, u64),
+        /// Collection was destroyed: collection id.
+        CollectionDestroyed(u64),
+        /// Collection access mode was changed: collection id, access mode.
+        AccessModeSet(u64, AccessMode),
+        /// Address was added to collection white list: collection id, address.
+        WhiteListAdded(u64, AccountId),
+        /// Address was removed from collection white list: collection id, address.
+        WhiteListRemoved(u64, AccountId),
+        /// Collection mint permission was changed: collection id, mint mode, per account mint limit.
+        MintPermissionSet(u64, bool, u32),
+        /// Contract sponsor was proposed: contract address, unconfirmed sponsor.
+        ContractSponsorSet(AccountId, AccountId),
+        /// Contract sponsorship was confirmed: contract address, sponsor.
+        ContractSponsorConfirmed(AccountId, AccountId),
+        /// Contract sponsor was removed: contract address.
+        ContractSponsorRemoved(AccountId),
+        /// Contract owner was set: contract address, owner.
+        ContractOwnerSet(AccountId, AccountId),
+        /// Contract sponsorship limits were changed: contract address, limits.
+        ContractSponsorshipLimitsSet(AccountId, ContractSponsorshipLimitsType),
+        /// Collection sponsorship limits were changed: collection id, limits.
+        SponsorshipLimitsSet(u64, SponsorshipLimitsType),
+        /// Collection fee model was changed: collection id, fee model.
+        FeeModelSet(u64, FeeModel),
+        /// Mask of calls paid by collection was changed: collection id, mask.
+        SponsoredCallsSet(u64, u32),
+        /// Funds were deposited to collection: collection id, depositor, amount.
+        CollectionDeposited(u64, AccountId, Balance),
+        /// Funds were withdrawn from collection deposit: collection id, recipient, amount.
+        CollectionWithdrawn(u64, AccountId, Balance),
+        /// Collection deposit can not pay the fee, sender pays it: collection id.
+        CollectionDepositExhausted(u64),
+        /// Transactions were bought for collection: collection id, buyer, number of transactions, price.
+        ResourcesBought(u64, AccountId, u64, Balance),
+        /// Collection creation fee was changed, None means runtime default: fee.
+        CreateCollectionFeeSet(Option<Balance>),
+        /// Flat fee of a call was changed, None means weight fee: call name, fee.
+        CallFlatFeeSet(Vec<u8>, Option<Balance>),
     }
 );
 
 decl_module! {
     pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 
+        type Error = Error<T>;
+
         fn deposit_event() = default;
 
+        fn on_initialize(_now: T::BlockNumber) -> Weight {
+            if DestroyQueueHead::get() == DestroyQueueTail::get() {
+                return T::DbWeight::get().reads(2);
+            }
+
+            let cleaned = Self::cleanup_destroyed_collections(COLLECTION_CLEANUP_ITEMS_LIMIT);
+            T::WeightInfo::destroy_collection(cleaned)
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            if StorageVersion::get() >= migration::STORAGE_VERSION {
+                return T::DbWeight::get().reads(1);
+            }
+
+            migration::migrate::<T>()
+        }
+
         // Create collection of NFT with given parameters
         //
         // @param customDataSz size of custom data in each collection item
         // returns collection ID
-        #[weight = 0]
+        #[weight = T::WeightInfo::create_collection()]
         pub fn create_collection(   origin,
                                     collection_name: Vec<u16>,
                                     collection_description: Vec<u16>,
@@ -197,24 +590,16 @@ decl_module! {
             };
 
             // check params
-            ensure!(decimal_points <= 4, "decimal_points parameter must be lower than 4"); 
-
-            let mut name = collection_name.to_vec();
-            name.push(0);
-            ensure!(name.len() <= 64, "Collection name can not be longer than 63 char");
+            ensure!(decimal_points <= 4, Error::<T>::CollectionDecimalPointLimitExceeded);
 
-            let mut description = collection_description.to_vec();
-            description.push(0);
-            ensure!(name.len() <= 256, "Collection description can not be longer than 255 char");
-
-            let mut prefix = token_prefix.to_vec();
-            prefix.push(0);
-            ensure!(prefix.len() <= 16, "Token prefix can not be longer than 15 char");
+            let name = Self::checked_collection_name(collection_name)?;
+            let description = Self::checked_collection_description(collection_description)?;
+            let prefix = Self::checked_token_prefix(token_prefix)?;
 
             // Generate next collection ID
             let next_id = NextCollectionID::get()
                 .checked_add(1)
-                .expect("collection id error");
+                .ok_or(Error::<T>::TotalCollectionsLimitExceeded)?;
 
             NextCollectionID::put(next_id);
 
@@ -231,6 +616,12 @@ decl_module! {
                 custom_data_size: custom_data_size,
                 sponsor: T::AccountId::default(),
                 unconfirmed_sponsor: T::AccountId::default(),
+                mint_mode: false,
+                per_account_mint_limit: 0,
+                fee_model: FeeModel::UserPaid,
+                unsponsored_calls: 0,
+                variable_data_size: 0,
+                variable_data_mode: VariableDataMode::ItemOwner,
             };
 
             // Add new collection to map
@@ -242,36 +633,63 @@ decl_module! {
             Ok(())
         }
 
-        #[weight = 0]
+        // Destroy collection and all its items
+        //
+        // Collection becomes unavailable immediately. Its items are removed
+        // in chunks of COLLECTION_CLEANUP_ITEMS_LIMIT entries: the first chunk
+        // in this call, the rest at the beginning of the following blocks.
+        #[weight = T::WeightInfo::destroy_collection(COLLECTION_CLEANUP_ITEMS_LIMIT)]
         pub fn destroy_collection(origin, collection_id: u64) -> DispatchResult {
 
             let sender = ensure_signed(origin)?;
-            Self::check_owner_permissions(collection_id, sender)?;
+            Self::check_owner_permissions(collection_id, sender.clone())?;
+
+            // Return the rest of collection deposit to the owner
+            let deposit_account = Self::collection_deposit_account(collection_id);
+            let deposit = T::Currency::free_balance(&deposit_account);
+            if !deposit.is_zero() {
+                T::Currency::transfer(&deposit_account, &sender, deposit, ExistenceRequirement::AllowDeath)?;
+            }
+
+            let target_collection = <Collection<T>>::get(collection_id);
+            <DestroyingCollection>::insert(collection_id, DestroyingCollectionType {
+                mode: target_collection.mode,
+                next_item_id: 1,
+                last_item_id: <ItemListIndex>::take(collection_id),
+            });
+            let tail = DestroyQueueTail::get();
+            <DestroyQueue>::insert(tail, collection_id);
+            DestroyQueueTail::put(tail + 1);
 
-            <AddressTokens<T>>::remove_prefix(collection_id);
-            <ApprovedList<T>>::remove_prefix(collection_id);
-            <Balance<T>>::remove_prefix(collection_id);
-            <ItemListIndex>::remove(collection_id);
             <AdminList<T>>::remove(collection_id);
             <Collection<T>>::remove(collection_id);
-            <WhiteList<T>>::remove(collection_id);
+            <SponsorshipLimits>::remove(collection_id);
+            <SponsoredFees<T>>::remove(collection_id);
+            <ResourceQuota>::remove(collection_id);
+
+            Self::cleanup_destroyed_collections(COLLECTION_CLEANUP_ITEMS_LIMIT);
+
+            Self::deposit_event(RawEvent::CollectionDestroyed(collection_id));
 
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::change_collection_owner()]
         pub fn change_collection_owner(origin, collection_id: u64, new_owner: T::AccountId) -> DispatchResult {
 
             let sender = ensure_signed(origin)?;
             Self::check_owner_permissions(collection_id, sender)?;
             let mut target_collection = <Collection<T>>::get(collection_id);
-            target_collection.owner = new_owner;
+            let old_owner = target_collection.owner.clone();
+            target_collection.owner = new_owner.clone();
             <Collection<T>>::insert(collection_id, target_collection);
 
+            Self::deposit_event(RawEvent::OwnerChanged(collection_id, old_owner, new_owner));
+
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::add_collection_admin(MAX_COLLECTION_ADMINS)]
         pub fn add_collection_admin(origin, collection_id: u64, new_admin_id: T::AccountId) -> DispatchResult {
 
             let sender = ensure_signed(origin)?;
@@ -281,16 +699,19 @@ decl_module! {
             if <AdminList<T>>::contains_key(collection_id)
             {
                 admin_arr = <AdminList<T>>::get(collection_id);
-                ensure!(!admin_arr.contains(&new_admin_id), "Account already has admin role");
+                ensure!(!admin_arr.contains(&new_admin_id), Error::<T>::AlreadyAdmin);
+                ensure!(admin_arr.len() < MAX_COLLECTION_ADMINS as usize, Error::<T>::CollectionAdminsLimitExceeded);
             }
 
-            admin_arr.push(new_admin_id);
+            admin_arr.push(new_admin_id.clone());
             <AdminList<T>>::insert(collection_id, admin_arr);
 
+            Self::deposit_event(RawEvent::AdminAdded(collection_id, new_admin_id));
+
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::remove_collection_admin(MAX_COLLECTION_ADMINS)]
         pub fn remove_collection_admin(origin, collection_id: u64, account_id: T::AccountId) -> DispatchResult {
 
             let sender = ensure_signed(origin)?;
@@ -299,71 +720,224 @@ decl_module! {
             if <AdminList<T>>::contains_key(collection_id)
             {
                 let mut admin_arr = <AdminList<T>>::get(collection_id);
-                admin_arr.retain(|i| *i != account_id);
-                <AdminList<T>>::insert(collection_id, admin_arr);
+                if admin_arr.contains(&account_id) {
+                    admin_arr.retain(|i| *i != account_id);
+                    <AdminList<T>>::insert(collection_id, admin_arr);
+
+                    Self::deposit_event(RawEvent::AdminRemoved(collection_id, account_id));
+                }
             }
 
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::set_collection_sponsor()]
         pub fn set_collection_sponsor(origin, collection_id: u64, new_sponsor: T::AccountId) -> DispatchResult {
 
             let sender = ensure_signed(origin)?;
-            ensure!(<Collection<T>>::contains_key(collection_id), "This collection does not exist");
+            Self::collection_exists(collection_id)?;
 
             let mut target_collection = <Collection<T>>::get(collection_id);
-            ensure!(sender == target_collection.owner, "You do not own this collection");
+            ensure!(sender == target_collection.owner, Error::<T>::NoPermission);
 
-            target_collection.unconfirmed_sponsor = new_sponsor;
+            target_collection.unconfirmed_sponsor = new_sponsor.clone();
             <Collection<T>>::insert(collection_id, target_collection);
 
+            Self::deposit_event(RawEvent::SponsorSet(collection_id, new_sponsor));
+
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::confirm_sponsorship()]
         pub fn confirm_sponsorship(origin, collection_id: u64) -> DispatchResult {
 
             let sender = ensure_signed(origin)?;
-            ensure!(<Collection<T>>::contains_key(collection_id), "This collection does not exist");
+            Self::collection_exists(collection_id)?;
 
             let mut target_collection = <Collection<T>>::get(collection_id);
-            ensure!(sender == target_collection.unconfirmed_sponsor, "This address is not set as sponsor, use setCollectionSponsor first");
+            ensure!(sender == target_collection.unconfirmed_sponsor, Error::<T>::ConfirmUnsetSponsorFail);
 
             target_collection.sponsor = target_collection.unconfirmed_sponsor;
             target_collection.unconfirmed_sponsor = T::AccountId::default();
+            // Confirmed sponsor pays for collection transactions unless
+            // collection owner selected another fee model
+            if target_collection.fee_model == FeeModel::UserPaid {
+                target_collection.fee_model = FeeModel::PayAsYouGo;
+            }
             <Collection<T>>::insert(collection_id, target_collection);
 
+            Self::deposit_event(RawEvent::SponsorConfirmed(collection_id, sender));
+
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::remove_collection_sponsor()]
         pub fn remove_collection_sponsor(origin, collection_id: u64) -> DispatchResult {
 
             let sender = ensure_signed(origin)?;
-            ensure!(<Collection<T>>::contains_key(collection_id), "This collection does not exist");
+            Self::collection_exists(collection_id)?;
 
             let mut target_collection = <Collection<T>>::get(collection_id);
-            ensure!(sender == target_collection.owner, "You do not own this collection");
+            ensure!(sender == target_collection.owner, Error::<T>::NoPermission);
 
             target_collection.sponsor = T::AccountId::default();
             <Collection<T>>::insert(collection_id, target_collection);
 
+            Self::deposit_event(RawEvent::SponsorRemoved(collection_id));
+
+            Ok(())
+        }
+
+        // Limit fees paid by collection sponsor. A transaction that exceeds
+        // the limits is paid by its sender.
+        #[weight = T::WeightInfo::set_sponsorship_limits()]
+        pub fn set_sponsorship_limits(origin, collection_id: u64, limits: SponsorshipLimitsType) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            Self::check_owner_permissions(collection_id, sender)?;
+
+            <SponsorshipLimits>::insert(collection_id, limits.clone());
+
+            Self::deposit_event(RawEvent::SponsorshipLimitsSet(collection_id, limits));
+
+            Ok(())
+        }
+
+        // Select who pays fees of collection transactions
+        #[weight = T::WeightInfo::select_fee_model()]
+        pub fn select_fee_model(origin, collection_id: u64, fee_model: FeeModel) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            Self::check_owner_or_admin_permissions(collection_id, sender)?;
+
+            let mut target_collection = <Collection<T>>::get(collection_id);
+            target_collection.fee_model = fee_model.clone();
+            <Collection<T>>::insert(collection_id, target_collection);
+
+            Self::deposit_event(RawEvent::FeeModelSet(collection_id, fee_model));
+
+            Ok(())
+        }
+
+        // Select item calls paid according to collection fee model, other
+        // calls are paid by their senders. See SPONSOR_* constants. Calls
+        // added in later versions are paid according to fee model until
+        // the owner unselects them.
+        #[weight = T::WeightInfo::set_sponsored_calls()]
+        pub fn set_sponsored_calls(origin, collection_id: u64, sponsored_calls: u32) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            Self::check_owner_permissions(collection_id, sender)?;
+            ensure!(sponsored_calls & !SPONSOR_ALL_CALLS == 0, Error::<T>::UnknownSponsoredCall);
+
+            let mut target_collection = <Collection<T>>::get(collection_id);
+            target_collection.unsponsored_calls = SPONSOR_ALL_CALLS & !sponsored_calls;
+            <Collection<T>>::insert(collection_id, target_collection);
+
+            Self::deposit_event(RawEvent::SponsoredCallsSet(collection_id, sponsored_calls));
+
+            Ok(())
+        }
+
+        // Prepay fees of collection transactions in PrepaidPlan fee model
+        #[weight = T::WeightInfo::deposit_to_collection()]
+        pub fn deposit_to_collection(origin, collection_id: u64, #[compact] value: BalanceOf<T>) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            Self::collection_exists(collection_id)?;
+
+            T::Currency::transfer(&sender, &Self::collection_deposit_account(collection_id), value, ExistenceRequirement::KeepAlive)?;
+
+            Self::deposit_event(RawEvent::CollectionDeposited(collection_id, sender, value));
+
+            Ok(())
+        }
+
+        #[weight = T::WeightInfo::withdraw_from_collection()]
+        pub fn withdraw_from_collection(origin, collection_id: u64, #[compact] value: BalanceOf<T>) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            Self::check_owner_permissions(collection_id, sender.clone())?;
+
+            T::Currency::transfer(&Self::collection_deposit_account(collection_id), &sender, value, ExistenceRequirement::AllowDeath)?;
+
+            Self::deposit_event(RawEvent::CollectionWithdrawn(collection_id, sender, value));
+
+            Ok(())
+        }
+
+        // Buy transactions for the collection in Resource Purchase fee model.
+        // Collection transactions use the quota instead of paying fees.
+        #[weight = T::WeightInfo::buy_resources()]
+        pub fn buy_resources(origin, collection_id: u64, tx_count: u64) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            Self::check_owner_permissions(collection_id, sender.clone())?;
+
+            let quota = ResourceQuota::get(collection_id)
+                .checked_add(tx_count)
+                .ok_or(Error::<T>::NumOverflow)?;
+            let price = T::ResourceTxPrice::get()
+                .checked_mul(&tx_count.saturated_into())
+                .ok_or(Error::<T>::NumOverflow)?;
+
+            let imbalance = T::Currency::withdraw(&sender, price, WithdrawReason::Fee.into(), ExistenceRequirement::KeepAlive)?;
+            T::OnResourcePayment::on_unbalanced(imbalance);
+
+            ResourceQuota::insert(collection_id, quota);
+
+            Self::deposit_event(RawEvent::ResourcesBought(collection_id, sender, tx_count, price));
+
+            Ok(())
+        }
+
+        // Override runtime default fee of collection creation, None restores the default
+        #[weight = T::WeightInfo::set_create_collection_fee()]
+        pub fn set_create_collection_fee(origin, fee: Option<BalanceOf<T>>) -> DispatchResult {
+
+            ensure_root(origin)?;
+
+            match fee {
+                Some(fee) => <CreateCollectionFee<T>>::put(fee),
+                None => <CreateCollectionFee<T>>::kill(),
+            }
+
+            Self::deposit_event(RawEvent::CreateCollectionFeeSet(fee));
+
+            Ok(())
+        }
+
+        // Charge a flat fee instead of the weight fee for a pallet call, None
+        // restores the weight fee. Tips are charged in addition to flat fees.
+        #[weight = T::WeightInfo::set_call_flat_fee()]
+        pub fn set_call_flat_fee(origin, call_name: Vec<u8>, fee: Option<BalanceOf<T>>) -> DispatchResult {
+
+            ensure_root(origin)?;
+            ensure!(Call::<T>::get_call_names().iter().any(|name| name.as_bytes() == &call_name[..]), Error::<T>::UnknownCall);
+
+            match fee {
+                Some(fee) => <CallFlatFee<T>>::insert(call_name.clone(), fee),
+                None => <CallFlatFee<T>>::remove(call_name.clone()),
+            }
+
+            Self::deposit_event(RawEvent::CallFlatFeeSet(call_name, fee));
+
             Ok(())
         }
         
-        #[weight = 0]
+        #[weight = T::WeightInfo::create_item(properties.len() as u32)]
         pub fn create_item(origin, collection_id: u64, properties: Vec<u8>, owner: T::AccountId) -> DispatchResult {
 
             let sender = ensure_signed(origin)?;
+            Self::collection_exists(collection_id)?;
 
-            // check size
             let target_collection = <Collection<T>>::get(collection_id);
-            ensure!(target_collection.custom_data_size >= properties.len() as u32, "Size of item is too large");
+            let public_mint = Self::check_create_permission(collection_id, &target_collection, &sender, sender == owner, 1)?;
 
-            Self::check_owner_or_admin_permissions(collection_id, sender.clone())?;
+            // check size
+            ensure!(target_collection.custom_data_size >= properties.len() as u32, Error::<T>::DataTooLarge);
+            Self::check_white_list(collection_id, &target_collection, &owner)?;
 
-            // TODO: implement other modes
             match target_collection.mode 
             {
                 CollectionMode::NFT(_) => {
@@ -371,12 +945,22 @@ decl_module! {
                     let item = NftItemType {
                         collection: collection_id,
                         owner: owner,
-                        data: properties.clone(),
+                        const_data: properties.clone(),
+                        variable_data: Vec::new(),
                     };
     
                     Self::add_nft_item(item)?;
     
                 },
+                CollectionMode::Fungible(_) => {
+                    let item = FungibleItemType {
+                        collection: collection_id,
+                        owner: owner,
+                        value: (10 as u128).pow(target_collection.decimal_points),
+                    };
+
+                    Self::add_fungible_item(item)?;
+                },
                 CollectionMode::ReFungible(_, _) => {
                     let mut owner_list = Vec::new();
                     let value = (10 as u128).pow(target_collection.decimal_points);
@@ -385,35 +969,127 @@ decl_module! {
                     let item = ReFungibleItemType {
                         collection: collection_id,
                         owner: owner_list,
-                        data: properties.clone()
+                        const_data: properties.clone(),
+                        variable_data: Vec::new(),
                     };
     
                     Self::add_refungible_item(item)?;
                 },
-                _ => { ensure!(1 == 0,"just error"); }
+                _ => { return Err(Error::<T>::UnexpectedCollectionType.into()); }
 
             };
 
+            if public_mint {
+                <AccountMinted<T>>::mutate(collection_id, sender, |minted| *minted = minted.saturating_add(1));
+            }
+
             // call event
             Self::deposit_event(RawEvent::ItemCreated(collection_id, <ItemListIndex>::get(collection_id)));
 
             Ok(())
         }
 
-        #[weight = 0]
-        pub fn burn_item(origin, collection_id: u64, item_id: u64) -> DispatchResult {
+        // Create several items in one call. Permissions are the same as in
+        // create_item, items get consecutive ids in the order of `items`.
+        #[weight = T::WeightInfo::create_multiple_items(
+            items.len() as u32,
+            items.iter().fold(0u32, |size, (properties, _)| size.saturating_add(properties.len() as u32))
+        )]
+        pub fn create_multiple_items(origin, collection_id: u64, items: Vec<(Vec<u8>, T::AccountId)>) -> DispatchResult {
 
             let sender = ensure_signed(origin)?;
-            let item_owner = Self::is_item_owner(sender.clone(), collection_id, item_id);
-            if !item_owner
-            {
-                Self::check_owner_or_admin_permissions(collection_id, sender.clone())?;
+            Self::collection_exists(collection_id)?;
+
+            ensure!(!items.is_empty(), Error::<T>::EmptyBatch);
+            ensure!(items.len() <= MAX_ITEMS_PER_BATCH as usize, Error::<T>::ItemsPerBatchLimitExceeded);
+            let count = items.len() as u32;
+
+            let target_collection = <Collection<T>>::get(collection_id);
+
+            let for_sender = items.iter().all(|(_, owner)| *owner == sender);
+            let public_mint = Self::check_create_permission(collection_id, &target_collection, &sender, for_sender, count)?;
+
+            let value = match target_collection.mode {
+                CollectionMode::NFT(_) => 1,
+                CollectionMode::Fungible(_) | CollectionMode::ReFungible(_, _) => (10 as u128).pow(target_collection.decimal_points),
+                _ => { return Err(Error::<T>::UnexpectedCollectionType.into()); }
+            };
+
+            // Items of every owner, checked before anything is written
+            let mut owners: BTreeMap<T::AccountId, Vec<u64>> = BTreeMap::new();
+            let first_id = <ItemListIndex>::get(collection_id).checked_add(1).ok_or(Error::<T>::NumOverflow)?;
+            let last_id = first_id.checked_add(count as u64 - 1).ok_or(Error::<T>::NumOverflow)?;
+            for (item_id, (properties, owner)) in (first_id..=last_id).zip(items.iter()) {
+                ensure!(target_collection.custom_data_size >= properties.len() as u32, Error::<T>::DataTooLarge);
+                if !owners.contains_key(owner) {
+                    Self::check_white_list(collection_id, &target_collection, owner)?;
+                }
+                owners.entry(owner.clone()).or_insert_with(Vec::new).push(item_id);
             }
+
+            let mut balances = Vec::new();
+            for (owner, item_ids) in owners.iter() {
+                let added = (item_ids.len() as u64).checked_mul(value as u64).ok_or(Error::<T>::NumOverflow)?;
+                let balance = <Balance<T>>::get(collection_id, owner).checked_add(added).ok_or(Error::<T>::NumOverflow)?;
+                balances.push(balance);
+            }
+
+            for ((owner, item_ids), balance) in owners.into_iter().zip(balances) {
+                <Balance<T>>::insert(collection_id, owner.clone(), balance);
+                <AddressTokens<T>>::mutate(collection_id, owner, |list| list.extend(item_ids));
+            }
+
+            for (item_id, (properties, owner)) in (first_id..=last_id).zip(items.into_iter()) {
+                match target_collection.mode {
+                    CollectionMode::NFT(_) => <NftItemList<T>>::insert(collection_id, item_id, NftItemType {
+                        collection: collection_id,
+                        owner: owner,
+                        const_data: properties,
+                        variable_data: Vec::new(),
+                    }),
+                    CollectionMode::Fungible(_) => <FungibleItemList<T>>::insert(collection_id, item_id, FungibleItemType {
+                        collection: collection_id,
+                        owner: owner,
+                        value: value,
+                    }),
+                    CollectionMode::ReFungible(_, _) => {
+                        let mut owner_list = Vec::new();
+                        owner_list.push(Ownership { owner: owner, fraction: value });
+                        <ReFungibleItemList<T>>::insert(collection_id, item_id, ReFungibleItemType {
+                            collection: collection_id,
+                            owner: owner_list,
+                            const_data: properties,
+                            variable_data: Vec::new(),
+                        });
+                    },
+                    CollectionMode::Invalid => (),
+                };
+            }
+            <ItemListIndex>::insert(collection_id, last_id);
+
+            if public_mint {
+                <AccountMinted<T>>::mutate(collection_id, sender, |minted| *minted = minted.saturating_add(count));
+            }
+
+            Self::deposit_event(RawEvent::ItemsCreated(collection_id, first_id, last_id));
+
+            Ok(())
+        }
+
+        #[weight = T::WeightInfo::burn_item()]
+        pub fn burn_item(origin, collection_id: u64, item_id: u64) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            Self::item_exists(collection_id, item_id)?;
+
+            Self::check_burn_permission(&sender, collection_id, item_id)?;
+
             let target_collection = <Collection<T>>::get(collection_id);
 
             match target_collection.mode 
             {
                 CollectionMode::NFT(_) => Self::burn_nft_item(collection_id, item_id)?,
+                CollectionMode::Fungible(_)  => Self::burn_fungible_item(collection_id, item_id)?,
                 CollectionMode::ReFungible(_, _)  => Self::burn_refungible_item(collection_id, item_id, sender.clone())?,
                 _ => ()
             };
@@ -424,101 +1100,156 @@ decl_module! {
             Ok(())
         }
 
-        #[weight = 0]
+        // A sender giving away its whole part of a re-fungible item loses
+        // all approvals it made for the item.
+        #[weight = T::WeightInfo::transfer(MAX_REFUNGIBLE_OWNERS).saturating_add(T::WeightInfo::clear_approvals(MAX_APPROVED_PER_ITEM))]
         pub fn transfer(origin, recipient: T::AccountId, collection_id: u64, item_id: u64, value: u64) -> DispatchResult {
 
             let sender = ensure_signed(origin)?;
-            ensure!(Self::is_item_owner(sender.clone(), collection_id, item_id), "Only item owner can call transfer method");
+            Self::check_transfer(&sender, &recipient, collection_id, item_id, value)?;
+            Self::do_transfer(sender, recipient, collection_id, item_id, value)
+        }
 
-            let target_collection = <Collection<T>>::get(collection_id);
+        // Transfer several items, possibly of different collections, in one call.
+        // Either all transfers succeed or none, every item can be used once per batch.
+        // Every transfer is weighed as a transfer of a re-fungible item part
+        // that drops all approvals of its sender.
+        #[weight = T::WeightInfo::transfer(MAX_REFUNGIBLE_OWNERS)
+            .saturating_add(T::WeightInfo::clear_approvals(MAX_APPROVED_PER_ITEM))
+            .saturating_mul(transfers.len() as Weight)]
+        pub fn transfer_batch(origin, transfers: Vec<(T::AccountId, u64, u64, u64)>) -> DispatchResult {
 
-            // TODO: implement other modes
-            match target_collection.mode 
-            {
-                CollectionMode::NFT(_) => Self::transfer_nft(collection_id, item_id, sender.clone(), recipient)?,
-                CollectionMode::ReFungible(_, _)  => Self::transfer_refungible(collection_id, item_id, value, sender.clone(), recipient)?,
-                _ => ()
-            };
+            let sender = ensure_signed(origin)?;
 
-            Ok(())
+            ensure!(!transfers.is_empty(), Error::<T>::EmptyBatch);
+            ensure!(transfers.len() <= MAX_ITEMS_PER_BATCH as usize, Error::<T>::ItemsPerBatchLimitExceeded);
+
+            // Items are distinct, so transfers do not affect checks of each other
+            let mut items = BTreeSet::new();
+            for (recipient, collection_id, item_id, value) in transfers.iter() {
+                ensure!(items.insert((*collection_id, *item_id)), Error::<T>::BatchItemDuplicated);
+                Self::check_transfer(&sender, recipient, *collection_id, *item_id, *value)?;
+            }
+
+            // Transfers already written are reverted if a later one fails
+            with_transaction(|| {
+                for (recipient, collection_id, item_id, value) in transfers.into_iter() {
+                    if let Err(err) = Self::do_transfer(sender.clone(), recipient, collection_id, item_id, value) {
+                        return TransactionOutcome::Rollback(Err(err));
+                    }
+                }
+                TransactionOutcome::Commit(Ok(()))
+            })
         }
 
-        #[weight = 0]
-        pub fn approve(origin, approved: T::AccountId, collection_id: u64, item_id: u64) -> DispatchResult {
+        // Set the amount of the item `approved` can transfer on behalf of the
+        // sender, replacing the previous allowance. Any non-zero amount allows
+        // to transfer an NFT, zero amount removes the approval.
+        #[weight = T::WeightInfo::approve(MAX_APPROVED_PER_ITEM)]
+        pub fn approve(origin, approved: T::AccountId, collection_id: u64, item_id: u64, amount: u64) -> DispatchResult {
 
             let sender = ensure_signed(origin)?;
+            Self::item_exists(collection_id, item_id)?;
 
-            let item_owner = Self::is_item_owner(sender.clone(), collection_id, item_id);
-            if !item_owner
-            {
-                Self::check_owner_or_admin_permissions(collection_id, sender.clone())?;
-            }
+            // Only the owner can let others move the item
+            ensure!(Self::is_item_owner(sender.clone(), collection_id, item_id), Error::<T>::NoPermission);
 
-            let list_exists = <ApprovedList<T>>::contains_key(collection_id, item_id);
-            if list_exists {
+            Self::set_allowance(collection_id, item_id, &sender, &approved, amount)?;
 
-                let mut list = <ApprovedList<T>>::get(collection_id, item_id);
-                let item_contains = list.contains(&approved.clone());
+            Self::deposit_event(RawEvent::Approved(collection_id, item_id, sender, approved, amount));
 
-                if !item_contains {
-                    list.push(approved.clone());
-                }
-            } else {
+            Ok(())
+        }
+
+        // Approve or disapprove an operator to transfer and burn all items
+        // of the sender in the collection, including items received later.
+        #[weight = T::WeightInfo::set_approval_for_all()]
+        pub fn set_approval_for_all(origin, collection_id: u64, operator: T::AccountId, approved: bool) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            Self::collection_exists(collection_id)?;
 
-                let mut itm = Vec::new();
-                itm.push(approved.clone());
-                <ApprovedList<T>>::insert(collection_id, item_id, itm);
+            let mut operators = <OperatorApprovals<T>>::get(collection_id, sender.clone());
+            if approved && !operators.contains(&operator) {
+                ensure!(operators.len() < MAX_OPERATORS_PER_ACCOUNT as usize, Error::<T>::OperatorsLimitExceeded);
+                operators.push(operator.clone());
+            }
+            if !approved {
+                operators.retain(|i| *i != operator);
+            }
+
+            if operators.is_empty() {
+                <OperatorApprovals<T>>::remove(collection_id, sender.clone());
+            } else {
+                <OperatorApprovals<T>>::insert(collection_id, sender.clone(), operators);
             }
 
+            Self::deposit_event(RawEvent::ApprovalForAll(collection_id, sender, operator, approved));
+
             Ok(())
         }
 
-        #[weight = 0]
-        pub fn transfer_from(origin, from: T::AccountId, recipient: T::AccountId, collection_id: u64, item_id: u64, value: u64 ) -> DispatchResult {
+        #[weight = T::WeightInfo::revoke_approval(MAX_APPROVED_PER_ITEM)]
+        pub fn revoke_approval(origin, approved: T::AccountId, collection_id: u64, item_id: u64) -> DispatchResult {
 
-            let mut approved: bool = false; 
             let sender = ensure_signed(origin)?;
-            let approved_list_exists = <ApprovedList<T>>::contains_key(collection_id, item_id);
-            if approved_list_exists
-            {
-                let list_itm = <ApprovedList<T>>::get(collection_id, item_id);
-                approved = list_itm.contains(&recipient.clone());
-            }
+            Self::item_exists(collection_id, item_id)?;
+            ensure!(Self::is_item_owner(sender.clone(), collection_id, item_id), Error::<T>::NoPermission);
 
-            if !approved
-            {
-                Self::check_owner_or_admin_permissions(collection_id, sender)?;
-            }
-            
-            let target_collection = <Collection<T>>::get(collection_id);
+            ensure!(Self::allowance(collection_id, item_id, &sender, &approved) > 0, Error::<T>::AddressNotApproved);
+            Self::set_allowance(collection_id, item_id, &sender, &approved, 0)?;
 
-            match target_collection.mode
-            {
-                CollectionMode::NFT(_) => Self::transfer_nft(collection_id, item_id, from, recipient)?,
-                CollectionMode::ReFungible(_, _)  => Self::transfer_refungible(collection_id, item_id, value, from, recipient)?,
-                // TODO: implement other modes
-                _ => ()
-            };
+            Self::deposit_event(RawEvent::ApprovalRevoked(collection_id, item_id, sender, approved));
 
             Ok(())
         }
 
-        #[weight = 0]
-        pub fn safe_transfer_from(origin, collection_id: u64, item_id: u64, new_owner: T::AccountId) -> DispatchResult {
+        // Remove all allowances of the sender for the item. Allowances of
+        // other owners of a re-fungible item stay.
+        #[weight = T::WeightInfo::clear_approvals(MAX_APPROVED_PER_ITEM)]
+        pub fn clear_approvals(origin, collection_id: u64, item_id: u64) -> DispatchResult {
 
-            // let no_perm_mes = "You do not have permissions to modify this collection";
-            // ensure!(<ApprovedList<T>>::contains_key((collection_id, item_id)), no_perm_mes);
-            // let list_itm = <ApprovedList<T>>::get((collection_id, item_id));
-            // ensure!(list_itm.contains(&new_owner.clone()), no_perm_mes);
+            let sender = ensure_signed(origin)?;
+            Self::item_exists(collection_id, item_id)?;
+            ensure!(Self::is_item_owner(sender.clone(), collection_id, item_id), Error::<T>::NoPermission);
 
-            // // on_nft_received  call
+            Self::remove_owner_approvals(collection_id, item_id, &sender)?;
 
-            // Self::transfer(origin, collection_id, item_id, new_owner)?;
+            Self::deposit_event(RawEvent::ApprovalsCleared(collection_id, item_id));
 
             Ok(())
         }
 
-        #[weight = 0]
+        #[weight = T::WeightInfo::transfer_from(MAX_REFUNGIBLE_OWNERS).saturating_add(T::WeightInfo::clear_approvals(MAX_APPROVED_PER_ITEM))]
+        pub fn transfer_from(origin, from: T::AccountId, recipient: T::AccountId, collection_id: u64, item_id: u64, value: u64 ) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            Self::transfer_from_internal(sender, from, recipient, collection_id, item_id, value)
+        }
+
+        /// Same as `transfer_from`, but a recipient contract has to accept the item
+        /// in its `on_nft_received` message, otherwise the transfer is reverted.
+        #[weight = T::WeightInfo::safe_transfer_from(MAX_REFUNGIBLE_OWNERS)
+            .saturating_add(T::WeightInfo::clear_approvals(MAX_APPROVED_PER_ITEM))
+            .saturating_add(T::OnNftReceivedGasLimit::get())]
+        pub fn safe_transfer_from(origin, from: T::AccountId, recipient: T::AccountId, collection_id: u64, item_id: u64, value: u64, data: Vec<u8>) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+
+            // The contract sees the item it owns, the transfer is reverted if it rejects the item
+            with_transaction(|| {
+                let mut result = Self::transfer_from_internal(sender.clone(), from.clone(), recipient.clone(), collection_id, item_id, value);
+                if result.is_ok() && T::Contracts::is_contract(&recipient) {
+                    result = Self::call_on_nft_received(&sender, &from, &recipient, collection_id, item_id, value, data);
+                }
+                match result {
+                    Ok(()) => TransactionOutcome::Commit(Ok(())),
+                    Err(err) => TransactionOutcome::Rollback(Err(err)),
+                }
+            })
+        }
+
+        #[weight = T::WeightInfo::set_offchain_schema(schema.len() as u32)]
         pub fn set_offchain_schema(
             origin,
             collection_id: u64,
@@ -528,49 +1259,911 @@ decl_module! {
             Self::check_owner_or_admin_permissions(collection_id, sender.clone())?;
             
             let mut target_collection = <Collection<T>>::get(collection_id);
-            target_collection.offchain_schema = schema;
+            target_collection.offchain_schema = schema.clone();
             <Collection<T>>::insert(collection_id, target_collection);
 
+            Self::deposit_event(RawEvent::OffchainSchemaSet(collection_id, schema));
+
             Ok(())        
         }
-    }
-}
 
-impl<T: Trait> Module<T> {
-
-    fn collection_exists(collection_id: u64) -> DispatchResult{
-        ensure!(<Collection<T>>::contains_key(collection_id), "This collection does not exist");
-        Ok(())
-    }
+        // Change collection name, same limits as in create_collection apply.
+        #[weight = T::WeightInfo::set_collection_name()]
+        pub fn set_collection_name(origin, collection_id: u64, collection_name: Vec<u16>) -> DispatchResult {
 
-    fn check_owner_permissions(collection_id: u64, subject: T::AccountId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::check_owner_or_admin_permissions(collection_id, sender)?;
+            let name = Self::checked_collection_name(collection_name)?;
 
-        Self::collection_exists(collection_id)?;
+            let mut target_collection = <Collection<T>>::get(collection_id);
+            target_collection.name = name.clone();
+            <Collection<T>>::insert(collection_id, target_collection);
 
-        let target_collection = <Collection<T>>::get(collection_id);
-        ensure!(subject == target_collection.owner, "You do not own this collection");
+            Self::deposit_event(RawEvent::CollectionNameSet(collection_id, name));
 
-        Ok(())
-    }
+            Ok(())
+        }
 
-    fn check_owner_or_admin_permissions(collection_id: u64, subject: T::AccountId) -> DispatchResult {
+        // Change collection description, same limits as in create_collection apply.
+        #[weight = T::WeightInfo::set_collection_description()]
+        pub fn set_collection_description(origin, collection_id: u64, collection_description: Vec<u16>) -> DispatchResult {
 
-        Self::collection_exists(collection_id)?;
+            let sender = ensure_signed(origin)?;
+            Self::check_owner_or_admin_permissions(collection_id, sender)?;
+            let description = Self::checked_collection_description(collection_description)?;
+
+            let mut target_collection = <Collection<T>>::get(collection_id);
+            target_collection.description = description.clone();
+            <Collection<T>>::insert(collection_id, target_collection);
+
+            Self::deposit_event(RawEvent::CollectionDescriptionSet(collection_id, description));
+
+            Ok(())
+        }
+
+        // Change token prefix of collection, same limits as in create_collection apply.
+        #[weight = T::WeightInfo::set_token_prefix()]
+        pub fn set_token_prefix(origin, collection_id: u64, token_prefix: Vec<u8>) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            Self::check_owner_or_admin_permissions(collection_id, sender)?;
+            let prefix = Self::checked_token_prefix(token_prefix)?;
+
+            let mut target_collection = <Collection<T>>::get(collection_id);
+            target_collection.token_prefix = prefix.clone();
+            <Collection<T>>::insert(collection_id, target_collection);
+
+            Self::deposit_event(RawEvent::TokenPrefixSet(collection_id, prefix));
+
+            Ok(())
+        }
+
+        // Set size limit of item variable data and who can change it.
+        // Data of existing items is kept even if it is larger than the new limit.
+        #[weight = T::WeightInfo::set_variable_data_policy()]
+        pub fn set_variable_data_policy(origin, collection_id: u64, variable_data_size: u32, mode: VariableDataMode) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            Self::check_owner_or_admin_permissions(collection_id, sender)?;
+            ensure!(variable_data_size <= MAX_VARIABLE_DATA_SIZE, Error::<T>::VariableDataSizeLimitExceeded);
+
+            let mut target_collection = <Collection<T>>::get(collection_id);
+            target_collection.variable_data_size = variable_data_size;
+            target_collection.variable_data_mode = mode.clone();
+            <Collection<T>>::insert(collection_id, target_collection);
+
+            Self::deposit_event(RawEvent::VariableDataPolicySet(collection_id, variable_data_size, mode));
+
+            Ok(())
+        }
+
+        // Change variable data of NFT or ReFungible item. Collection
+        // variable data mode defines who can call it.
+        #[weight = T::WeightInfo::set_variable_data(data.len() as u32)]
+        pub fn set_variable_data(origin, collection_id: u64, item_id: u64, data: Vec<u8>) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            Self::item_exists(collection_id, item_id)?;
+
+            let target_collection = <Collection<T>>::get(collection_id);
+            Self::check_variable_data_permission(&sender, collection_id, &target_collection, item_id)?;
+            ensure!(target_collection.variable_data_size >= data.len() as u32, Error::<T>::DataTooLarge);
+
+            match target_collection.mode
+            {
+                CollectionMode::NFT(_) => <NftItemList<T>>::mutate(collection_id, item_id, |item| item.variable_data = data),
+                CollectionMode::ReFungible(_, _) => <ReFungibleItemList<T>>::mutate(collection_id, item_id, |item| item.variable_data = data),
+                _ => { return Err(Error::<T>::UnexpectedCollectionType.into()); }
+            };
+
+            Self::deposit_event(RawEvent::VariableDataSet(collection_id, item_id));
+
+            Ok(())
+        }
+
+        // Change collection access mode. In WhiteList mode items can be
+        // created for and transferred to white listed addresses only.
+        #[weight = T::WeightInfo::set_public_access_mode()]
+        pub fn set_public_access_mode(origin, collection_id: u64, mode: AccessMode) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            Self::check_owner_or_admin_permissions(collection_id, sender)?;
+
+            let mut target_collection = <Collection<T>>::get(collection_id);
+            target_collection.access = mode.clone();
+            <Collection<T>>::insert(collection_id, target_collection);
+
+            Self::deposit_event(RawEvent::AccessModeSet(collection_id, mode));
+
+            Ok(())
+        }
+
+        #[weight = T::WeightInfo::add_to_white_list()]
+        pub fn add_to_white_list(origin, collection_id: u64, address: T::AccountId) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            Self::check_owner_or_admin_permissions(collection_id, sender)?;
+
+            <WhiteList<T>>::insert(collection_id, address.clone(), true);
+
+            Self::deposit_event(RawEvent::WhiteListAdded(collection_id, address));
+
+            Ok(())
+        }
+
+        #[weight = T::WeightInfo::remove_from_white_list()]
+        pub fn remove_from_white_list(origin, collection_id: u64, address: T::AccountId) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            Self::check_owner_or_admin_permissions(collection_id, sender)?;
+
+            if <WhiteList<T>>::contains_key(collection_id, address.clone()) {
+                <WhiteList<T>>::remove(collection_id, address.clone());
+
+                Self::deposit_event(RawEvent::WhiteListRemoved(collection_id, address));
+            }
+
+            Ok(())
+        }
+
+        // Allow or forbid item creation by accounts that are neither owner nor
+        // admins. Such accounts create items for themselves only, and in
+        // WhiteList access mode they must be white listed. A non-zero
+        // per_account_mint_limit caps the number of items each of them can create.
+        #[weight = T::WeightInfo::set_mint_permission()]
+        pub fn set_mint_permission(origin, collection_id: u64, mint_mode: bool, per_account_mint_limit: u32) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            Self::check_owner_or_admin_permissions(collection_id, sender)?;
+
+            let mut target_collection = <Collection<T>>::get(collection_id);
+            target_collection.mint_mode = mint_mode;
+            target_collection.per_account_mint_limit = per_account_mint_limit;
+            <Collection<T>>::insert(collection_id, target_collection);
+
+            Self::deposit_event(RawEvent::MintPermissionSet(collection_id, mint_mode, per_account_mint_limit));
+
+            Ok(())
+        }
+
+        // Propose a sponsor that pays fees for calls of the contract.
+        // Contract owner is the account that instantiated the contract.
+        #[weight = T::WeightInfo::set_contract_sponsor()]
+        pub fn set_contract_sponsor(origin, contract_address: T::AccountId, new_sponsor: T::AccountId) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            Self::check_contract_owner(&contract_address, &sender)?;
+
+            <UnconfirmedContractSponsor<T>>::insert(contract_address.clone(), new_sponsor.clone());
+
+            Self::deposit_event(RawEvent::ContractSponsorSet(contract_address, new_sponsor));
+
+            Ok(())
+        }
+
+        #[weight = T::WeightInfo::confirm_contract_sponsorship()]
+        pub fn confirm_contract_sponsorship(origin, contract_address: T::AccountId) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            ensure!(<UnconfirmedContractSponsor<T>>::contains_key(contract_address.clone()), Error::<T>::ConfirmUnsetSponsorFail);
+            ensure!(sender == <UnconfirmedContractSponsor<T>>::get(contract_address.clone()), Error::<T>::ConfirmUnsetSponsorFail);
+
+            <ContractSponsor<T>>::insert(contract_address.clone(), sender.clone());
+            <UnconfirmedContractSponsor<T>>::remove(contract_address.clone());
+
+            Self::deposit_event(RawEvent::ContractSponsorConfirmed(contract_address, sender));
+
+            Ok(())
+        }
+
+        #[weight = T::WeightInfo::remove_contract_sponsor()]
+        pub fn remove_contract_sponsor(origin, contract_address: T::AccountId) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            Self::check_contract_owner(&contract_address, &sender)?;
+
+            <ContractSponsor<T>>::remove(contract_address.clone());
+            <UnconfirmedContractSponsor<T>>::remove(contract_address.clone());
+
+            Self::deposit_event(RawEvent::ContractSponsorRemoved(contract_address));
+
+            Ok(())
+        }
+
+        // Set owner of a contract, e.g. of a contract instantiated before
+        // contract owners were recorded.
+        #[weight = T::WeightInfo::set_contract_owner()]
+        pub fn set_contract_owner(origin, contract_address: T::AccountId, owner: T::AccountId) -> DispatchResult {
+
+            ensure_root(origin)?;
+
+            <ContractOwner<T>>::insert(contract_address.clone(), owner.clone());
+
+            Self::deposit_event(RawEvent::ContractOwnerSet(contract_address, owner));
+
+            Ok(())
+        }
+
+        #[weight = T::WeightInfo::set_contract_sponsorship_limits()]
+        pub fn set_contract_sponsorship_limits(origin, contract_address: T::AccountId, limits: ContractSponsorshipLimitsType) -> DispatchResult {
+
+            let sender = ensure_signed(origin)?;
+            Self::check_contract_owner(&contract_address, &sender)?;
+
+            <ContractSponsorshipLimits<T>>::insert(contract_address.clone(), limits.clone());
+
+            Self::deposit_event(RawEvent::ContractSponsorshipLimitsSet(contract_address, limits));
+
+            Ok(())
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+
+    fn collection_exists(collection_id: u64) -> DispatchResult{
+        ensure!(<Collection<T>>::contains_key(collection_id), Error::<T>::CollectionNotFound);
+        Ok(())
+    }
+
+    fn item_exists(collection_id: u64, item_id: u64) -> DispatchResult {
+
+        Self::collection_exists(collection_id)?;
+
+        let exists = match <Collection<T>>::get(collection_id).mode {
+            CollectionMode::NFT(_) => <NftItemList<T>>::contains_key(collection_id, item_id),
+            CollectionMode::Fungible(_) => <FungibleItemList<T>>::contains_key(collection_id, item_id),
+            CollectionMode::ReFungible(_, _) => <ReFungibleItemList<T>>::contains_key(collection_id, item_id),
+            CollectionMode::Invalid => false
+        };
+        ensure!(exists, Error::<T>::ItemNotFound);
+
+        Ok(())
+    }
+
+    // Collection name with null escape char appended, must be valid UTF-16
+    fn checked_collection_name(collection_name: Vec<u16>) -> Result<Vec<u16>, DispatchError> {
+        ensure!(collection_name.len() < MAX_COLLECTION_NAME_LENGTH, Error::<T>::CollectionNameLimitExceeded);
+        ensure!(Self::is_valid_utf16(&collection_name), Error::<T>::CollectionNameInvalid);
+
+        let mut name = collection_name;
+        name.push(0);
+        Ok(name)
+    }
+
+    // Collection description with null escape char appended, must be valid UTF-16
+    fn checked_collection_description(collection_description: Vec<u16>) -> Result<Vec<u16>, DispatchError> {
+        ensure!(collection_description.len() < MAX_COLLECTION_DESCRIPTION_LENGTH, Error::<T>::CollectionDescriptionLimitExceeded);
+        ensure!(Self::is_valid_utf16(&collection_description), Error::<T>::CollectionDescriptionInvalid);
+
+        let mut description = collection_description;
+        description.push(0);
+        Ok(description)
+    }
+
+    // Token prefix with null escape char appended
+    fn checked_token_prefix(token_prefix: Vec<u8>) -> Result<Vec<u8>, DispatchError> {
+        ensure!(token_prefix.len() < MAX_TOKEN_PREFIX_LENGTH, Error::<T>::CollectionTokenPrefixLimitExceeded);
+
+        let mut prefix = token_prefix;
+        prefix.push(0);
+        Ok(prefix)
+    }
+
+    // No unpaired surrogates
+    fn is_valid_utf16(s: &[u16]) -> bool {
+        core::char::decode_utf16(s.iter().cloned()).all(|c| c.is_ok())
+    }
+
+    fn check_owner_permissions(collection_id: u64, subject: T::AccountId) -> DispatchResult {
+
+        Self::collection_exists(collection_id)?;
+
+        let target_collection = <Collection<T>>::get(collection_id);
+        ensure!(subject == target_collection.owner, Error::<T>::NoPermission);
+
+        Ok(())
+    }
+
+    fn check_owner_or_admin_permissions(collection_id: u64, subject: T::AccountId) -> DispatchResult {
+
+        Self::collection_exists(collection_id)?;
 
         let target_collection = <Collection<T>>::get(collection_id);
         let is_owner = subject == target_collection.owner;
 
-        let no_perm_mes = "You do not have permissions to modify this collection";
         let exists = <AdminList<T>>::contains_key(collection_id);
 
         if !is_owner
         {
-            ensure!(exists, no_perm_mes);
-            ensure!(<AdminList<T>>::get(collection_id).contains(&subject), no_perm_mes);
+            ensure!(exists, Error::<T>::NoPermission);
+            ensure!(<AdminList<T>>::get(collection_id).contains(&subject), Error::<T>::NoPermission);
+        }
+        Ok(())
+    }
+
+    /// Remove up to `limit` items and per-account entries of destroyed collections.
+    /// Returns the number of processed items and entries.
+    fn cleanup_destroyed_collections(limit: u32) -> u32 {
+
+        let mut head = DestroyQueueHead::get();
+        let mut processed: u32 = 0;
+
+        while processed < limit {
+            let collection_id = match <DestroyQueue>::get(head) {
+                Some(collection_id) => collection_id,
+                None => break
+            };
+            let mut state = <DestroyingCollection>::get(collection_id);
+
+            while processed < limit && state.next_item_id <= state.last_item_id {
+                // Approvals can outnumber owners of the item, they are removed
+                // first and may take several chunks
+                processed += Self::remove_item_approvals(collection_id, state.next_item_id, limit - processed);
+                if processed >= limit {
+                    break;
+                }
+                processed += Self::remove_collection_item(collection_id, state.next_item_id, &state.mode);
+                state.next_item_id += 1;
+            }
+
+            // Entries of accounts that own nothing anymore and per-account
+            // settings of the collection. All of them are removed when every
+            // map returns less than it was allowed to.
+            processed += Self::remove_entries::<AddressTokens<T>, _, _>(collection_id, limit - processed);
+            processed += Self::remove_entries::<Balance<T>, _, _>(collection_id, limit - processed);
+            processed += Self::remove_entries::<WhiteList<T>, _, _>(collection_id, limit - processed);
+            processed += Self::remove_entries::<AccountMinted<T>, _, _>(collection_id, limit - processed);
+            processed += Self::remove_entries::<CreateItemSponsorBasket<T>, _, _>(collection_id, limit - processed);
+            processed += Self::remove_entries::<TransferSponsorBasket<T>, _, _>(collection_id, limit - processed);
+            processed += Self::remove_entries::<OperatorApprovals<T>, _, _>(collection_id, limit - processed);
+
+            if processed >= limit {
+                <DestroyingCollection>::insert(collection_id, state);
+                break;
+            }
+
+            <DestroyingCollection>::remove(collection_id);
+            <DestroyQueue>::remove(head);
+            head += 1;
+        }
+
+        DestroyQueueHead::put(head);
+        processed
+    }
+
+    // Remove up to `limit` entries with the given first key, e.g. entries of
+    // a destroyed collection. Returns the number of removed entries.
+    fn remove_entries<S, K1, K2, V>(first_key: K1, limit: u32) -> u32
+        where S: IterableStorageDoubleMap<K1, K2, V>, K1: FullCodec, K2: FullCodec, V: FullCodec
+    {
+        let keys: Vec<K2> = S::iter_prefix(&first_key)
+            .take(limit as usize)
+            .map(|(key, _)| key)
+            .collect();
+        for key in keys.iter() {
+            S::remove(&first_key, key);
         }
+
+        keys.len() as u32
+    }
+
+    // Remove up to `limit` approval entries of an item of a destroyed
+    // collection. Returns the number of removed entries, all of them are
+    // removed if it is less than `limit`.
+    fn remove_item_approvals(collection_id: u64, item_id: u64, limit: u32) -> u32 {
+
+        let item = (collection_id, item_id);
+        let mut removed = Self::remove_entries::<Allowances<T>, _, _, _>(item, limit);
+        removed += Self::remove_entries::<OwnerApprovedList<T>, _, _, _>(item, limit - removed);
+        removed += Self::remove_entries::<ApprovalOwnerCount<T>, _, _, _>(item, limit - removed);
+        if removed < limit && <ApprovedList<T>>::contains_key(collection_id, item_id) {
+            <ApprovedList<T>>::remove(collection_id, item_id);
+            removed += 1;
+        }
+
+        removed
+    }
+
+    // Remove an item of a destroyed collection with entries of its owners.
+    // Returns the number of owners, at least one for a missing item.
+    fn remove_collection_item(collection_id: u64, item_id: u64, mode: &CollectionMode) -> u32 {
+
+        let mut owners: Vec<T::AccountId> = Vec::new();
+        match mode {
+            CollectionMode::NFT(_) if <NftItemList<T>>::contains_key(collection_id, item_id) => {
+                owners.push(<NftItemList<T>>::take(collection_id, item_id).owner);
+            },
+            CollectionMode::Fungible(_) if <FungibleItemList<T>>::contains_key(collection_id, item_id) => {
+                owners.push(<FungibleItemList<T>>::take(collection_id, item_id).owner);
+            },
+            CollectionMode::ReFungible(_, _) if <ReFungibleItemList<T>>::contains_key(collection_id, item_id) => {
+                owners = <ReFungibleItemList<T>>::take(collection_id, item_id).owner.into_iter().map(|i| i.owner).collect();
+            },
+            _ => ()
+        };
+
+        let removed = owners.len().max(1) as u32;
+        for owner in owners {
+            <AddressTokens<T>>::remove(collection_id, owner.clone());
+            <Balance<T>>::remove(collection_id, owner);
+        }
+
+        removed
+    }
+
+    fn check_white_list(collection_id: u64, collection: &CollectionType<T::AccountId>, address: &T::AccountId) -> DispatchResult {
+
+        if collection.access == AccessMode::WhiteList {
+            ensure!(<WhiteList<T>>::get(collection_id, address), Error::<T>::AddressNotInWhiteList);
+        }
+
         Ok(())
     }
 
+    /// Collection ID, and item ID with SPONSOR_* bit of a call paid according to
+    /// collection fee model. Item is None for item creation.
+    fn sponsored_collection_call(call: &Call<T>) -> Option<(u64, Option<(u64, u32)>)> {
+
+        let (collection_id, item_id, call_bit) = match call {
+            Call::create_item(collection_id, _properties, _owner) => (*collection_id, None, SPONSOR_CREATE_ITEM),
+            Call::create_multiple_items(collection_id, _items) => (*collection_id, None, SPONSOR_CREATE_ITEM),
+            Call::burn_item(collection_id, item_id) => (*collection_id, Some(*item_id), SPONSOR_BURN_ITEM),
+            Call::transfer(_recipient, collection_id, item_id, _value) => (*collection_id, Some(*item_id), SPONSOR_TRANSFER),
+            Call::approve(_approved, collection_id, item_id, _amount) => (*collection_id, Some(*item_id), SPONSOR_APPROVE),
+            Call::revoke_approval(_approved, collection_id, item_id) => (*collection_id, Some(*item_id), SPONSOR_APPROVE),
+            Call::clear_approvals(collection_id, item_id) => (*collection_id, Some(*item_id), SPONSOR_APPROVE),
+            Call::transfer_from(_from, _recipient, collection_id, item_id, _value) => (*collection_id, Some(*item_id), SPONSOR_TRANSFER_FROM),
+            Call::safe_transfer_from(_from, _recipient, collection_id, item_id, _value, _data) => (*collection_id, Some(*item_id), SPONSOR_SAFE_TRANSFER_FROM),
+            Call::set_variable_data(collection_id, item_id, _data) => (*collection_id, Some(*item_id), SPONSOR_SET_VARIABLE_DATA),
+            _ => return None,
+        };
+
+        if <Collection<T>>::get(collection_id).unsponsored_calls & call_bit != 0 {
+            return None;
+        }
+
+        Some((collection_id, item_id.map(|item_id| (item_id, call_bit))))
+    }
+
+    /// Fee of collection creation, charged in addition to the weight fee.
+    pub fn create_collection_fee() -> BalanceOf<T> {
+        <CreateCollectionFee<T>>::get().unwrap_or_else(T::CollectionCreationFee::get)
+    }
+
+    /// Number of transactions a collection call counts as in resource quota
+    /// and sponsorship limits: one for every created item.
+    fn collection_call_tx_count(call: &Call<T>) -> u32 {
+        match call {
+            Call::create_multiple_items(_collection_id, items) => items.len() as u32,
+            _ => 1,
+        }
+    }
+
+    /// Use `tx_count` transactions of the collection resource quota. Returns false
+    /// if the collection does not use Resource Purchase fee model or the quota is
+    /// not enough.
+    fn use_resource_quota(collection_id: u64, tx_count: u32) -> bool {
+
+        if <Collection<T>>::get(collection_id).fee_model != FeeModel::ResourcePurchase {
+            return false;
+        }
+
+        let quota = ResourceQuota::get(collection_id);
+        if quota < tx_count as u64 {
+            return false;
+        }
+
+        ResourceQuota::insert(collection_id, quota - tx_count as u64);
+        true
+    }
+
+    /// Account that holds prepaid fees of the collection.
+    pub fn collection_deposit_account(collection_id: u64) -> T::AccountId {
+        T::ModuleId::get().into_sub_account(collection_id)
+    }
+
+    /// Account that pays `fee` for a collection transaction according to collection
+    /// fee model, or default account if the transaction is paid by its sender.
+    /// Prepaid deposit is subject to the same limits as collection sponsor.
+    fn collection_fee_payer(collection_id: u64, who: &T::AccountId, item: Option<(u64, u32)>, fee: u128) -> T::AccountId {
+
+        match <Collection<T>>::get(collection_id).fee_model {
+            FeeModel::PayAsYouGo => Self::check_collection_sponsorship(collection_id, who, item, fee),
+            FeeModel::PrepaidPlan if Self::within_sponsorship_limits(collection_id, who, item, fee) =>
+                Self::collection_deposit_account(collection_id),
+            _ => T::AccountId::default(),
+        }
+    }
+
+    /// Collection sponsor that pays `fee` for a sponsored call, or default account if
+    /// the collection is not sponsored or the call exceeds sponsorship limits.
+    /// `item` is the item of the call with SPONSOR_* bit of the call, or None for
+    /// create_item calls of `who`.
+    fn check_collection_sponsorship(collection_id: u64, who: &T::AccountId, item: Option<(u64, u32)>, fee: u128) -> T::AccountId {
+
+        let sponsor = <Collection<T>>::get(collection_id).sponsor;
+        if sponsor == T::AccountId::default() || !Self::within_sponsorship_limits(collection_id, who, item, fee) {
+            return T::AccountId::default();
+        }
+
+        sponsor
+    }
+
+    // Whether the collection can pay `fee` for a call of `who` without
+    // exceeding its sponsorship limits. Item calls of different kinds
+    // have separate timeouts.
+    fn within_sponsorship_limits(collection_id: u64, who: &T::AccountId, item: Option<(u64, u32)>, fee: u128) -> bool {
+
+        let limits = <SponsorshipLimits>::get(collection_id);
+        if limits.max_tx_fee > 0 && fee > limits.max_tx_fee {
+            return false;
+        }
+
+        let now = <system::Module<T>>::block_number();
+        let (last_sponsored, timeout) = match item {
+            Some(item) => (<TransferSponsorBasket<T>>::get(collection_id, item), limits.transfer_timeout),
+            None => (<CreateItemSponsorBasket<T>>::get(collection_id, who), limits.create_item_timeout),
+        };
+        if let Some(last_sponsored) = last_sponsored {
+            if timeout > 0 && now < last_sponsored.saturating_add(T::BlockNumber::from(timeout)) {
+                return false;
+            }
+        }
+
+        let (block, spent) = <SponsoredFees<T>>::get(collection_id);
+        let spent = if block == now { spent } else { 0 };
+        limits.block_fee_limit == 0 || spent.saturating_add(fee) <= limits.block_fee_limit
+    }
+
+    // Count a call paid for `who` by the collection towards sponsorship limits.
+    // Creation of `tx_count` items delays the next sponsored creation of `who`
+    // by create item timeout for every item.
+    fn record_collection_sponsorship(collection_id: u64, who: &T::AccountId, item: Option<(u64, u32)>, tx_count: u32, fee: u128) {
+
+        let now = <system::Module<T>>::block_number();
+        let (block, spent) = <SponsoredFees<T>>::get(collection_id);
+        let spent = if block == now { spent } else { 0 };
+
+        match item {
+            Some(item) => <TransferSponsorBasket<T>>::insert(collection_id, item, now),
+            None => {
+                let timeout = <SponsorshipLimits>::get(collection_id).create_item_timeout;
+                let delay = T::BlockNumber::from(timeout.saturating_mul(tx_count.saturating_sub(1)));
+                <CreateItemSponsorBasket<T>>::insert(collection_id, who, now.saturating_add(delay));
+            },
+        }
+        <SponsoredFees<T>>::insert(collection_id, (now, spent.saturating_add(fee)));
+    }
+
+    /// Contract sponsor that pays `fee` for a call of `who`, or default account if
+    /// the contract is not sponsored or the call exceeds sponsorship limits.
+    fn contract_fee_payer(contract_address: &T::AccountId, who: &T::AccountId, fee: u128) -> T::AccountId {
+
+        let sponsor = <ContractSponsor<T>>::get(contract_address);
+        if sponsor == T::AccountId::default() {
+            return sponsor;
+        }
+
+        let limits = <ContractSponsorshipLimits<T>>::get(contract_address);
+        if limits.max_tx_fee > 0 && fee > limits.max_tx_fee {
+            return T::AccountId::default();
+        }
+
+        let now = <system::Module<T>>::block_number();
+        if let Some(last_sponsored) = <ContractSponsorBasket<T>>::get(contract_address, who) {
+            if limits.call_timeout > 0 && now < last_sponsored.saturating_add(T::BlockNumber::from(limits.call_timeout)) {
+                return T::AccountId::default();
+            }
+        }
+
+        let (block, spent) = <ContractSponsoredFees<T>>::get(contract_address);
+        let spent = if block == now { spent } else { 0 };
+        if limits.block_fee_limit > 0 && spent.saturating_add(fee) > limits.block_fee_limit {
+            return T::AccountId::default();
+        }
+
+        sponsor
+    }
+
+    // Count a call of `who` paid by the contract sponsor towards sponsorship limits
+    fn record_contract_sponsorship(contract_address: &T::AccountId, who: &T::AccountId, fee: u128) {
+
+        let now = <system::Module<T>>::block_number();
+        let (block, spent) = <ContractSponsoredFees<T>>::get(contract_address);
+        let spent = if block == now { spent } else { 0 };
+
+        <ContractSponsorBasket<T>>::insert(contract_address, who, now);
+        <ContractSponsoredFees<T>>::insert(contract_address, (now, spent.saturating_add(fee)));
+    }
+
+    fn check_contract_owner(contract_address: &T::AccountId, subject: &T::AccountId) -> DispatchResult {
+
+        ensure!(<ContractOwner<T>>::contains_key(contract_address), Error::<T>::NoPermission);
+        ensure!(<ContractOwner<T>>::get(contract_address) == *subject, Error::<T>::NoPermission);
+
+        Ok(())
+    }
+
+    // Non-admin `sender` may create `count` more items for itself
+    fn check_public_mint(collection_id: u64, collection: &CollectionType<T::AccountId>, sender: &T::AccountId, count: u32) -> DispatchResult {
+
+        ensure!(collection.mint_mode, Error::<T>::NoPermission);
+
+        if collection.per_account_mint_limit > 0 {
+            let minted = <AccountMinted<T>>::get(collection_id, sender).saturating_add(count);
+            ensure!(minted <= collection.per_account_mint_limit, Error::<T>::AccountMintLimitExceeded);
+        }
+
+        Ok(())
+    }
+
+    // Owner and admins can create items for anyone. Other accounts can
+    // only create items for themselves if the collection allows it.
+    // Returns whether the items are minted publicly.
+    fn check_create_permission(collection_id: u64, collection: &CollectionType<T::AccountId>, sender: &T::AccountId, for_sender: bool, count: u32) -> Result<bool, DispatchError> {
+
+        let public_mint = Self::check_owner_or_admin_permissions(collection_id, sender.clone()).is_err();
+        if public_mint {
+            ensure!(for_sender, Error::<T>::NoPermission);
+            Self::check_public_mint(collection_id, collection, sender, count)?;
+        }
+
+        Ok(public_mint)
+    }
+
+    fn check_burn_permission(sender: &T::AccountId, collection_id: u64, item_id: u64) -> DispatchResult {
+
+        // Re-fungible items have several owners and the call does not name
+        // the one whose part is burnt, so operators can not burn them. An
+        // operator can move the part to itself with transfer_from first.
+        let item_owner = Self::is_item_owner(sender.clone(), collection_id, item_id);
+        let is_operator = match <Collection<T>>::get(collection_id).mode {
+            CollectionMode::NFT(_) => Self::is_approved_for_all(collection_id, &<NftItemList<T>>::get(collection_id, item_id).owner, sender),
+            CollectionMode::Fungible(_) => Self::is_approved_for_all(collection_id, &<FungibleItemList<T>>::get(collection_id, item_id).owner, sender),
+            _ => false
+        };
+        if !item_owner && !is_operator
+        {
+            Self::check_owner_or_admin_permissions(collection_id, sender.clone())?;
+        }
+
+        Ok(())
+    }
+
+    fn check_variable_data_permission(sender: &T::AccountId, collection_id: u64, collection: &CollectionType<T::AccountId>, item_id: u64) -> DispatchResult {
+
+        let allowed = match collection.variable_data_mode {
+            VariableDataMode::ItemOwner => Self::is_item_owner(sender.clone(), collection_id, item_id),
+            VariableDataMode::Admin => Self::check_owner_or_admin_permissions(collection_id, sender.clone()).is_ok(),
+            VariableDataMode::ItemOwnerAndAdmin => Self::is_item_owner(sender.clone(), collection_id, item_id) ||
+                Self::check_owner_or_admin_permissions(collection_id, sender.clone()).is_ok(),
+        };
+        ensure!(allowed, Error::<T>::NoPermission);
+
+        Ok(())
+    }
+
+    // Permission checks of a collection call, without changing storage. Calls
+    // that fail them are paid by the sender, not from collection funds.
+    fn check_call_permission(call: &Call<T>, sender: &T::AccountId) -> DispatchResult {
+
+        match call {
+            Call::create_item(collection_id, _properties, owner) => {
+                Self::collection_exists(*collection_id)?;
+                let collection = <Collection<T>>::get(collection_id);
+                Self::check_create_permission(*collection_id, &collection, sender, sender == owner, 1).map(|_| ())
+            },
+            Call::create_multiple_items(collection_id, items) => {
+                Self::collection_exists(*collection_id)?;
+                let collection = <Collection<T>>::get(collection_id);
+                let for_sender = items.iter().all(|(_, owner)| owner == sender);
+                Self::check_create_permission(*collection_id, &collection, sender, for_sender, items.len() as u32).map(|_| ())
+            },
+            Call::burn_item(collection_id, item_id) => {
+                Self::item_exists(*collection_id, *item_id)?;
+                Self::check_burn_permission(sender, *collection_id, *item_id)
+            },
+            Call::transfer(recipient, collection_id, item_id, value) => Self::check_transfer(sender, recipient, *collection_id, *item_id, *value),
+            Call::approve(_, collection_id, item_id, _) |
+            Call::revoke_approval(_, collection_id, item_id) |
+            Call::clear_approvals(collection_id, item_id) => {
+                Self::item_exists(*collection_id, *item_id)?;
+                ensure!(Self::is_item_owner(sender.clone(), *collection_id, *item_id), Error::<T>::NoPermission);
+                Ok(())
+            },
+            Call::transfer_from(from, recipient, collection_id, item_id, value) |
+            Call::safe_transfer_from(from, recipient, collection_id, item_id, value, _) => {
+                Self::check_transfer_from(sender, from, recipient, *collection_id, *item_id, *value).map(|_| ())
+            },
+            Call::set_variable_data(collection_id, item_id, _data) => {
+                Self::item_exists(*collection_id, *item_id)?;
+                let collection = <Collection<T>>::get(collection_id);
+                Self::check_variable_data_permission(sender, *collection_id, &collection, *item_id)
+            },
+            _ => Ok(()),
+        }
+    }
+
+    // All checks of `transfer`, without changing storage
+    fn check_transfer(sender: &T::AccountId, recipient: &T::AccountId, collection_id: u64, item_id: u64, value: u64) -> DispatchResult {
+
+        Self::item_exists(collection_id, item_id)?;
+        ensure!(Self::is_item_owner(sender.clone(), collection_id, item_id), Error::<T>::NoPermission);
+
+        let target_collection = <Collection<T>>::get(collection_id);
+        Self::check_white_list(collection_id, &target_collection, recipient)?;
+
+        match target_collection.mode {
+            CollectionMode::Fungible(_) => {
+                ensure!(value > 0, Error::<T>::ZeroTransferValue);
+                let amount = <FungibleItemList<T>>::get(collection_id, item_id).value;
+                ensure!(amount >= value.into(), Error::<T>::InsufficientFraction);
+            },
+            CollectionMode::ReFungible(_, _) => {
+                ensure!(value > 0, Error::<T>::ZeroTransferValue);
+                let full_item = <ReFungibleItemList<T>>::get(collection_id, item_id);
+                let amount = full_item.owner.iter().find(|i| i.owner == *sender).map(|i| i.fraction).unwrap_or(0);
+                ensure!(amount >= value.into(), Error::<T>::InsufficientFraction);
+
+                let splits_item = amount != value.into() && !full_item.owner.iter().any(|i| i.owner == *recipient);
+                ensure!(!splits_item || full_item.owner.len() < MAX_REFUNGIBLE_OWNERS as usize, Error::<T>::RefungibleOwnersLimitExceeded);
+            },
+            _ => ()
+        };
+
+        Ok(())
+    }
+
+    // All checks of `transfer_from`, returns allowance left after the transfer
+    // if the caller spends it
+    fn check_transfer_from(sender: &T::AccountId, from: &T::AccountId, recipient: &T::AccountId, collection_id: u64, item_id: u64, value: u64) -> Result<Option<u64>, DispatchError> {
+
+        Self::item_exists(collection_id, item_id)?;
+
+        // The caller moves its own item, has allowance of `from` for the item or is an operator of `from`
+        let uses_allowance = sender != from && !Self::is_approved_for_all(collection_id, from, sender);
+        let spent = match <Collection<T>>::get(collection_id).mode {
+            CollectionMode::NFT(_) => 1,
+            _ => value
+        };
+        let remaining = if uses_allowance {
+            let allowance = Self::allowance(collection_id, item_id, from, sender);
+            ensure!(allowance > 0, Error::<T>::NoPermission);
+            ensure!(allowance >= spent, Error::<T>::AllowanceExceeded);
+            Some(allowance - spent)
+        } else {
+            None
+        };
+
+        Self::check_transfer(from, recipient, collection_id, item_id, value)?;
+
+        Ok(remaining)
+    }
+
+    fn transfer_from_internal(sender: T::AccountId, from: T::AccountId, recipient: T::AccountId, collection_id: u64, item_id: u64, value: u64) -> DispatchResult {
+
+        let remaining = Self::check_transfer_from(&sender, &from, &recipient, collection_id, item_id, value)?;
+        Self::do_transfer(from.clone(), recipient, collection_id, item_id, value)?;
+
+        // Allowance is decreased, operator approval stays. Transfer of
+        // the whole item removes all its allowances.
+        if let Some(remaining) = remaining {
+            if <Allowances<T>>::contains_key((collection_id, item_id), (from.clone(), sender.clone())) {
+                Self::set_allowance(collection_id, item_id, &from, &sender, remaining)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Input of the `on_nft_received` call: selector followed by encoded
+    /// `(operator, from, collection_id, item_id, value, data)`.
+    pub fn on_nft_received_input(operator: &T::AccountId, from: &T::AccountId, collection_id: u64, item_id: u64, value: u64, data: Vec<u8>) -> Vec<u8> {
+        let mut input = ON_NFT_RECEIVED_SELECTOR.to_vec();
+        (operator, from, collection_id, item_id, value, data).encode_to(&mut input);
+        input
+    }
+
+    fn call_on_nft_received(operator: &T::AccountId, from: &T::AccountId, recipient: &T::AccountId, collection_id: u64, item_id: u64, value: u64, data: Vec<u8>) -> DispatchResult {
+
+        let input = Self::on_nft_received_input(operator, from, collection_id, item_id, value, data);
+        let accepted = T::Contracts::call(operator.clone(), recipient.clone(), T::OnNftReceivedGasLimit::get(), input)
+            .map(|output| output[..] == ON_NFT_RECEIVED_SELECTOR[..])
+            .unwrap_or(false);
+        ensure!(accepted, Error::<T>::ReceiverRejectedItem);
+
+        Ok(())
+    }
+
+    fn do_transfer(sender: T::AccountId, recipient: T::AccountId, collection_id: u64, item_id: u64, value: u64) -> DispatchResult {
+
+        match <Collection<T>>::get(collection_id).mode
+        {
+            CollectionMode::NFT(_) => Self::transfer_nft(collection_id, item_id, sender, recipient)?,
+            CollectionMode::Fungible(_)  => Self::transfer_fungible(collection_id, item_id, value, sender, recipient)?,
+            CollectionMode::ReFungible(_, _)  => Self::transfer_refungible(collection_id, item_id, value, sender, recipient)?,
+            _ => ()
+        };
+
+        Ok(())
+    }
+
+    /// Amount of the item `spender` can transfer on behalf of `owner`.
+    pub fn allowance(collection_id: u64, item_id: u64, owner: &T::AccountId, spender: &T::AccountId) -> u64 {
+        <Allowances<T>>::get((collection_id, item_id), (owner.clone(), spender.clone()))
+    }
+
+    // Set allowance and keep ApprovedList equal to the set of spenders with
+    // a non-zero allowance of any owner. Every owner can approve up to
+    // MAX_APPROVED_PER_OWNER spenders, so the list of an item holds at most
+    // MAX_APPROVED_PER_ITEM spenders.
+    fn set_allowance(collection_id: u64, item_id: u64, owner: &T::AccountId, spender: &T::AccountId, amount: u64) -> DispatchResult {
+
+        let item = (collection_id, item_id);
+        let mut spenders = <OwnerApprovedList<T>>::get(item, owner);
+        let approved = spenders.contains(spender);
+
+        if amount > 0 {
+            if !approved {
+                ensure!(spenders.len() < MAX_APPROVED_PER_OWNER as usize, Error::<T>::ApprovedListLimitExceeded);
+                spenders.push(spender.clone());
+                <OwnerApprovedList<T>>::insert(item, owner, spenders);
+
+                let owners = <ApprovalOwnerCount<T>>::get(item, spender);
+                if owners == 0 {
+                    <ApprovedList<T>>::mutate(collection_id, item_id, |list| list.push(spender.clone()));
+                }
+                <ApprovalOwnerCount<T>>::insert(item, spender, owners + 1);
+            }
+            <Allowances<T>>::insert(item, (owner.clone(), spender.clone()), amount);
+        } else if approved {
+            <Allowances<T>>::remove(item, (owner.clone(), spender.clone()));
+
+            spenders.retain(|i| i != spender);
+            if spenders.is_empty() {
+                <OwnerApprovedList<T>>::remove(item, owner);
+            } else {
+                <OwnerApprovedList<T>>::insert(item, owner, spenders);
+            }
+
+            let owners = <ApprovalOwnerCount<T>>::get(item, spender).saturating_sub(1);
+            if owners > 0 {
+                <ApprovalOwnerCount<T>>::insert(item, spender, owners);
+            } else {
+                <ApprovalOwnerCount<T>>::remove(item, spender);
+                let mut list = <ApprovedList<T>>::get(collection_id, item_id);
+                list.retain(|i| i != spender);
+                if list.is_empty() {
+                    <ApprovedList<T>>::remove(collection_id, item_id);
+                } else {
+                    <ApprovedList<T>>::insert(collection_id, item_id, list);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn remove_owner_approvals(collection_id: u64, item_id: u64, owner: &T::AccountId) -> DispatchResult {
+        for spender in <OwnerApprovedList<T>>::get((collection_id, item_id), owner) {
+            Self::set_allowance(collection_id, item_id, owner, &spender, 0)?;
+        }
+        Ok(())
+    }
+
+    fn remove_approvals(collection_id: u64, item_id: u64) {
+        <ApprovedList<T>>::remove(collection_id, item_id);
+        <Allowances<T>>::remove_prefix((collection_id, item_id));
+        <OwnerApprovedList<T>>::remove_prefix((collection_id, item_id));
+        <ApprovalOwnerCount<T>>::remove_prefix((collection_id, item_id));
+    }
+
+    /// Whether `operator` can transfer all items of `owner` in the collection.
+    pub fn is_approved_for_all(collection_id: u64, owner: &T::AccountId, operator: &T::AccountId) -> bool {
+        <OperatorApprovals<T>>::get(collection_id, owner).contains(operator)
+    }
+
     fn is_item_owner(subject: T::AccountId, collection_id: u64, item_id: u64) -> bool{
 
         let target_collection = <Collection<T>>::get(collection_id);
@@ -583,11 +2176,142 @@ impl<T: Trait> Module<T> {
         }
     }
 
+    fn add_fungible_item(item: FungibleItemType<T::AccountId>) -> DispatchResult {
+
+        let current_index = <ItemListIndex>::get(item.collection)
+        .checked_add(1)
+        .ok_or(Error::<T>::NumOverflow)?;
+        let itemcopy = item.clone();
+
+        let value = item.value as u64;
+        let owner = item.owner.clone();
+
+        Self::add_token_index(item.collection, current_index, owner.clone())?;
+
+        <ItemListIndex>::insert(item.collection, current_index);
+        <FungibleItemList<T>>::insert(item.collection, current_index, itemcopy);
+
+        // Update balance
+        let new_balance = <Balance<T>>::get(item.collection, owner.clone()).checked_add(value).ok_or(Error::<T>::NumOverflow)?;
+        <Balance<T>>::insert(item.collection, owner.clone(), new_balance);
+
+        Ok(())
+    }
+
+    fn burn_fungible_item(collection_id: u64, item_id: u64) -> DispatchResult {
+
+        let item = <FungibleItemList<T>>::get(collection_id, item_id);
+        Self::remove_token_index(collection_id, item_id, item.owner.clone())?;
+
+        // update balance
+        let new_balance = <Balance<T>>::get(collection_id, item.owner.clone()).checked_sub(item.value as u64).ok_or(Error::<T>::NumOverflow)?;
+        <Balance<T>>::insert(collection_id, item.owner.clone(), new_balance);
+        <FungibleItemList<T>>::remove(collection_id, item_id);
+        Self::remove_approvals(collection_id, item_id);
+
+        Ok(())
+    }
+
+    fn transfer_fungible(collection_id: u64, item_id: u64, value: u64, owner: T::AccountId, new_owner: T::AccountId) -> DispatchResult {
+
+        let full_item = <FungibleItemList<T>>::get(collection_id, item_id);
+        ensure!(owner == full_item.owner, Error::<T>::NoPermission);
+        ensure!(value > 0, Error::<T>::ZeroTransferValue);
+
+        let amount = full_item.value;
+        ensure!(amount >= value.into(), Error::<T>::InsufficientFraction);
+
+        // Fungible items of one owner are interchangeable, the value goes to
+        // an item the recipient already has
+        let recipient_item = <AddressTokens<T>>::get(collection_id, new_owner.clone())
+            .into_iter()
+            .find(|id| *id != item_id);
+
+        // update balance
+        let balance_old_owner = <Balance<T>>::get(collection_id, owner.clone()).checked_sub(value).ok_or(Error::<T>::NumOverflow)?;
+        <Balance<T>>::insert(collection_id, owner.clone(), balance_old_owner);
+
+        let balance_new_owner = <Balance<T>>::get(collection_id, new_owner.clone()).checked_add(value).ok_or(Error::<T>::NumOverflow)?;
+        <Balance<T>>::insert(collection_id, new_owner.clone(), balance_new_owner);
+
+        let val128: u128 = value.into();
+
+        // transfer, the recipient gets the value in `received_item_id`
+        let mut source_removed = false;
+        let received_item_id = if let Some(recipient_item_id) = recipient_item
+        {
+            // merge amount into the item of the recipient
+            let mut recipient_full_item = <FungibleItemList<T>>::get(collection_id, recipient_item_id);
+            recipient_full_item.value = recipient_full_item.value.checked_add(val128).ok_or(Error::<T>::NumOverflow)?;
+            <FungibleItemList<T>>::insert(collection_id, recipient_item_id, recipient_full_item);
+
+            if amount == val128 {
+                // nothing left in the source item
+                Self::remove_token_index(collection_id, item_id, owner.clone())?;
+                <FungibleItemList<T>>::remove(collection_id, item_id);
+                Self::remove_approvals(collection_id, item_id);
+                source_removed = true;
+            } else {
+                let mut new_full_item = full_item.clone();
+                new_full_item.value -= val128;
+                <FungibleItemList<T>>::insert(collection_id, item_id, new_full_item);
+            }
+
+            recipient_item_id
+        }
+        else if amount == val128
+        {
+            // change owner
+            let mut new_full_item = full_item.clone();
+            new_full_item.owner = new_owner.clone();
+            <FungibleItemList<T>>::insert(collection_id, item_id, new_full_item);
+
+            // update index collection
+            Self::move_token_index(collection_id, item_id, owner.clone(), new_owner.clone())?;
+
+            // reset approved list
+            Self::remove_approvals(collection_id, item_id);
+
+            item_id
+        }
+        else
+        {
+            // separate amount into a new item of the recipient
+            let mut new_full_item = full_item.clone();
+            new_full_item.value -= val128;
+            <FungibleItemList<T>>::insert(collection_id, item_id, new_full_item);
+
+            let current_index = <ItemListIndex>::get(collection_id)
+            .checked_add(1)
+            .ok_or(Error::<T>::NumOverflow)?;
+
+            let item = FungibleItemType {
+                collection: collection_id,
+                owner: new_owner.clone(),
+                value: val128,
+            };
+
+            Self::add_token_index(collection_id, current_index, new_owner.clone())?;
+            <ItemListIndex>::insert(collection_id, current_index);
+            <FungibleItemList<T>>::insert(collection_id, current_index, item);
+            Self::deposit_event(RawEvent::ItemCreated(collection_id, current_index));
+
+            current_index
+        };
+
+        Self::deposit_event(RawEvent::Transfer(collection_id, received_item_id, owner, new_owner, value));
+        if source_removed {
+            Self::deposit_event(RawEvent::ItemDestroyed(collection_id, item_id));
+        }
+
+        Ok(())
+    }
+
     fn add_refungible_item(item: ReFungibleItemType<T::AccountId>) -> DispatchResult {
 
         let current_index = <ItemListIndex>::get(item.collection)
         .checked_add(1)
-        .expect("Item list index id error");
+        .ok_or(Error::<T>::NumOverflow)?;
         let itemcopy = item.clone();
 
         let value = item.owner.first().unwrap().fraction as u64;
@@ -599,7 +2323,7 @@ impl<T: Trait> Module<T> {
         <ReFungibleItemList<T>>::insert(item.collection, current_index, itemcopy);  
         
         // Update balance
-       let new_balance = <Balance<T>>::get(item.collection, owner.clone()).checked_add(value).unwrap();
+       let new_balance = <Balance<T>>::get(item.collection, owner.clone()).checked_add(value).ok_or(Error::<T>::NumOverflow)?;
        <Balance<T>>::insert(item.collection, owner.clone(), new_balance);
 
         Ok(())
@@ -608,11 +2332,12 @@ impl<T: Trait> Module<T> {
     fn burn_refungible_item(collection_id: u64, item_id: u64, owner: T::AccountId) -> DispatchResult {
   
         let collection = <ReFungibleItemList<T>>::get(collection_id, item_id);
-        let item = collection.owner.iter().filter(|&i| i.owner == owner).next().unwrap();
+        let item = collection.owner.iter().filter(|&i| i.owner == owner).next()
+            .ok_or(Error::<T>::NoPermission)?;
         Self::remove_token_index(collection_id, item_id, owner)?;
 
         // update balance
-        let new_balance = <Balance<T>>::get(collection_id, item.owner.clone()).checked_sub(item.fraction as u64).unwrap();
+        let new_balance = <Balance<T>>::get(collection_id, item.owner.clone()).checked_sub(item.fraction as u64).ok_or(Error::<T>::NumOverflow)?;
         <Balance<T>>::insert(collection_id, item.owner.clone(), new_balance);
 
         // TODO
@@ -624,20 +2349,24 @@ impl<T: Trait> Module<T> {
     fn transfer_refungible(collection_id: u64, item_id: u64, value: u64, owner: T::AccountId, new_owner: T::AccountId) -> DispatchResult {
 
         let full_item = <ReFungibleItemList<T>>::get(collection_id, item_id);
-        let item = full_item.owner.iter().filter(|i| i.owner == owner).next().unwrap();
+        let item = full_item.owner.iter().filter(|i| i.owner == owner).next()
+            .ok_or(Error::<T>::NoPermission)?;
         let amount = item.fraction;
 
-        ensure!(amount >= value.into(),"Item balance not enouth");
+        ensure!(amount >= value.into(), Error::<T>::InsufficientFraction);
+
+        let new_owner_has_account = full_item.owner.iter().any(|i| i.owner == new_owner);
+        let splits_item = amount != value.into() && !new_owner_has_account;
+        ensure!(!splits_item || full_item.owner.len() < MAX_REFUNGIBLE_OWNERS as usize, Error::<T>::RefungibleOwnersLimitExceeded);
 
         // update balance
-        let balance_old_owner = <Balance<T>>::get(collection_id, item.owner.clone()).checked_sub(value).unwrap();
+        let balance_old_owner = <Balance<T>>::get(collection_id, item.owner.clone()).checked_sub(value).ok_or(Error::<T>::NumOverflow)?;
         <Balance<T>>::insert(collection_id, item.owner.clone(), balance_old_owner);
 
-        let balance_new_owner = <Balance<T>>::get(collection_id, new_owner.clone()).checked_add(value).unwrap();
+        let balance_new_owner = <Balance<T>>::get(collection_id, new_owner.clone()).checked_add(value).ok_or(Error::<T>::NumOverflow)?;
         <Balance<T>>::insert(collection_id, new_owner.clone(), balance_new_owner);
 
         let old_owner = item.owner.clone();
-        let new_owner_has_account = full_item.owner.iter().any(|i| i.owner == new_owner);
         let val64 = value.into();
 
         // transfer
@@ -651,6 +2380,10 @@ impl<T: Trait> Module<T> {
 
             // update index collection
             Self::move_token_index(collection_id, item_id, old_owner.clone(), new_owner.clone())?;
+
+            // the old owner is no longer an owner, drop its approvals to
+            // keep the approved list bounded by the owners limit
+            Self::remove_owner_approvals(collection_id, item_id, &old_owner)?;
         }
         else
         {
@@ -672,6 +2405,8 @@ impl<T: Trait> Module<T> {
             <ReFungibleItemList<T>>::insert(collection_id, item_id, new_full_item);
         }
 
+        Self::deposit_event(RawEvent::Transfer(collection_id, item_id, owner, new_owner, value));
+
         Ok(())
     }
     
@@ -679,7 +2414,7 @@ impl<T: Trait> Module<T> {
 
         let current_index = <ItemListIndex>::get(item.collection)
         .checked_add(1)
-        .expect("Item list index id error");
+        .ok_or(Error::<T>::NumOverflow)?;
         let itemcopy = item.clone();
 
         Self::add_token_index(item.collection, current_index, item.owner.clone())?;
@@ -688,7 +2423,7 @@ impl<T: Trait> Module<T> {
         <NftItemList<T>>::insert(item.collection, current_index, item);
 
         // Update balance
-        let new_balance = <Balance<T>>::get(itemcopy.collection, itemcopy.owner.clone()).checked_add(1).unwrap();
+        let new_balance = <Balance<T>>::get(itemcopy.collection, itemcopy.owner.clone()).checked_add(1).ok_or(Error::<T>::NumOverflow)?;
         <Balance<T>>::insert(itemcopy.collection, itemcopy.owner.clone(), new_balance);
 
         Ok(())
@@ -700,9 +2435,10 @@ impl<T: Trait> Module<T> {
         Self::remove_token_index(collection_id, item_id, item.owner.clone())?;
 
         // update balance
-        let new_balance = <Balance<T>>::get(collection_id, item.owner.clone()).checked_sub(1).unwrap();
+        let new_balance = <Balance<T>>::get(collection_id, item.owner.clone()).checked_sub(1).ok_or(Error::<T>::NumOverflow)?;
         <Balance<T>>::insert(collection_id, item.owner.clone(), new_balance);
         <NftItemList<T>>::remove(collection_id, item_id);
+        Self::remove_approvals(collection_id, item_id);
 
         Ok(())
     }
@@ -711,13 +2447,13 @@ impl<T: Trait> Module<T> {
 
         let mut item = <NftItemList<T>>::get(collection_id, item_id);
 
-        ensure!(sender == item.owner,"sender parameter and item owner must be equal");
+        ensure!(sender == item.owner, Error::<T>::NoPermission);
 
         // update balance
-        let balance_old_owner = <Balance<T>>::get(collection_id, item.owner.clone()).checked_sub(1).unwrap();
+        let balance_old_owner = <Balance<T>>::get(collection_id, item.owner.clone()).checked_sub(1).ok_or(Error::<T>::NumOverflow)?;
         <Balance<T>>::insert(collection_id, item.owner.clone(), balance_old_owner);
 
-        let balance_new_owner = <Balance<T>>::get(collection_id, new_owner.clone()).checked_add(1).unwrap();
+        let balance_new_owner = <Balance<T>>::get(collection_id, new_owner.clone()).checked_add(1).ok_or(Error::<T>::NumOverflow)?;
         <Balance<T>>::insert(collection_id, new_owner.clone(), balance_new_owner);
 
         // change owner
@@ -726,11 +2462,12 @@ impl<T: Trait> Module<T> {
         <NftItemList<T>>::insert(collection_id, item_id, item);
 
         // update index collection
-        Self::move_token_index(collection_id, item_id, old_owner, new_owner.clone())?;
+        Self::move_token_index(collection_id, item_id, old_owner.clone(), new_owner.clone())?;
 
         // reset approved list
-        let itm: Vec<T::AccountId> = Vec::new();
-        <ApprovedList<T>>::insert(collection_id, item_id, itm);
+        Self::remove_approvals(collection_id, item_id);
+
+        Self::deposit_event(RawEvent::Transfer(collection_id, item_id, old_owner, new_owner, 1));
 
         Ok(())
     }
@@ -801,6 +2538,34 @@ type NegativeImbalanceOf<T> = <<T as transaction_payment::Trait>::Currency as Cu
 
 
 
+/// Fee handler that gives `TreasuryShare` of fees to `TreasuryAccount` (network owner)
+/// and the rest to the author of the block. Without known block author everything
+/// goes to `TreasuryAccount`.
+pub struct FeeSplit<T, TreasuryAccount, TreasuryShare, BlockAuthor>(
+	sp_std::marker::PhantomData<(T, TreasuryAccount, TreasuryShare, BlockAuthor)>
+);
+
+impl<T, TreasuryAccount, TreasuryShare, BlockAuthor> OnUnbalanced<NegativeImbalanceOf<T>>
+	for FeeSplit<T, TreasuryAccount, TreasuryShare, BlockAuthor>
+where
+	T: transaction_payment::Trait,
+	TreasuryAccount: Get<T::AccountId>,
+	TreasuryShare: Get<Perbill>,
+	BlockAuthor: FindAuthor<T::AccountId>,
+{
+	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T>) {
+		let treasury_part = TreasuryShare::get() * amount.peek();
+		let (to_treasury, to_author) = amount.split(treasury_part);
+
+		let digest = <system::Module<T>>::digest();
+		let author = BlockAuthor::find_author(digest.logs.iter().filter_map(|item| item.as_pre_runtime()))
+			.unwrap_or_else(TreasuryAccount::get);
+
+		<T as transaction_payment::Trait>::Currency::resolve_creating(&author, to_author);
+		<T as transaction_payment::Trait>::Currency::resolve_creating(&TreasuryAccount::get(), to_treasury);
+	}
+}
+
 /// Require the transactor pay for themselves and maybe include a tip to gain additional priority
 /// in the queue.
 #[derive(Encode, Decode, Clone, Eq, PartialEq)]
@@ -817,8 +2582,8 @@ impl<T:Trait + transaction_payment::Trait + Send + Sync> sp_std::fmt::Debug for
 	}
 }
 
-impl<T:Trait + transaction_payment::Trait + Send + Sync> ChargeTransactionPayment<T> where
-	T::Call: Dispatchable<Info=DispatchInfo, PostInfo=PostDispatchInfo> + IsSubType<Module<T>, T>,
+impl<T:Trait + transaction_payment::Trait + contracts::Trait + Send + Sync> ChargeTransactionPayment<T> where
+	T::Call: Dispatchable<Info=DispatchInfo, PostInfo=PostDispatchInfo> + IsSubType<Module<T>, T> + IsSubType<contracts::Module<T>, T>,
 	BalanceOf<T>: Send + Sync + FixedPointOperand,
 {
 	/// utility constructor. Used only in client/factory code.
@@ -842,32 +2607,56 @@ impl<T:Trait + transaction_payment::Trait + Send + Sync> ChargeTransactionPaymen
         call: &T::Call,
 		info: &DispatchInfoOf<T::Call>,
 		len: usize,
-	) -> Result<(BalanceOf<T>, Option<NegativeImbalanceOf<T>>), TransactionValidityError> {
+	) -> Result<(BalanceOf<T>, BalanceOf<T>, T::AccountId, Option<NegativeImbalanceOf<T>>), TransactionValidityError> {
         let tip = self.0;
 
-        // Set fee based on call type. Creating collection costs 1 Unique.
-        // All other transactions have traditional fees so far
-        let fee = match call.is_sub_type() {
-            Some(Call::create_collection(..)) => <BalanceOf<T>>::from(1_000_000_000),
-            _ => Self::traditional_fee(len, info, tip)
-
-            // Flat fee model, use only for testing purposes
-            // _ => <BalanceOf<T>>::from(100)
+        // Set fee based on call type. Returns total fee, its weight based part,
+        // which is refunded after dispatch if the call used less weight, and
+        // the account that paid the fee.
+        // Calls with flat fee set by governance are charged flat fee and tip
+        // instead of weight fee. Creating collection costs additional fee.
+        let weight_fee = Self::traditional_fee(len, info, tip);
+        let nft_call = IsSubType::<Module<T>, T>::is_sub_type(call);
+        let (fee, weight_fee) = match nft_call.and_then(|nft_call| <Module<T>>::call_flat_fee(nft_call.get_call_name().as_bytes().to_vec())) {
+            Some(flat_fee) => (flat_fee.saturating_add(tip), Zero::zero()),
+            None => (weight_fee, weight_fee),
+        };
+        let fee = match nft_call {
+            Some(Call::create_collection(..)) => <Module<T>>::create_collection_fee().saturating_add(fee),
+            _ => fee,
         };
 
         // Determine who is paying transaction fee based on ecnomic model
-        // Parse call to extract collection ID and access collection sponsor
-        let sponsor: T::AccountId = match call.is_sub_type() {
-            Some(Call::create_item(collection_id, _properties, _owner)) => {
-                <Collection<T>>::get(collection_id).sponsor
-            },
-            Some(Call::transfer(_new_owner, collection_id, _item_id, _value)) => {
-                <Collection<T>>::get(collection_id).sponsor
-            },
+        // Parse call to extract collection ID and item ID of collection transactions.
+        // Calls the sender is not permitted to make are paid by the sender and
+        // do not use collection quota or funds.
+        let collection_call = nft_call
+            .filter(|nft_call| <Module<T>>::check_call_permission(nft_call, who).is_ok())
+            .and_then(<Module<T>>::sponsored_collection_call);
+
+        let tx_count = nft_call.map(<Module<T>>::collection_call_tx_count).unwrap_or(1);
+
+        // Transactions bought in advance are not charged
+        if let Some((collection_id, _)) = collection_call {
+            if <Module<T>>::use_resource_quota(collection_id, tx_count) {
+                return Ok((Zero::zero(), Zero::zero(), who.clone(), None));
+            }
+        }
 
-            _ => T::AccountId::default()
+        let mut sponsor: T::AccountId = match collection_call {
+            Some((collection_id, item)) => <Module<T>>::collection_fee_payer(collection_id, who, item, fee.saturated_into()),
+            None => T::AccountId::default()
         };
 
+        // Calls of sponsored contracts are paid by contract sponsor
+        let mut sponsored_contract = None;
+        if let Some(contracts::Call::call(dest, _value, _gas_limit, _data)) = IsSubType::<contracts::Module<T>, T>::is_sub_type(call) {
+            if let Ok(contract_address) = <T as system::Trait>::Lookup::lookup(dest.clone()) {
+                sponsor = <Module<T>>::contract_fee_payer(&contract_address, who, fee.saturated_into());
+                sponsored_contract = Some(contract_address);
+            }
+        }
+
         let mut who_pays_fee: T::AccountId = sponsor.clone();
         if sponsor == T::AccountId::default() {
             who_pays_fee = who.clone();
@@ -875,34 +2664,64 @@ impl<T:Trait + transaction_payment::Trait + Send + Sync> ChargeTransactionPaymen
 
 		// Only mess with balances if fee is not zero.
 		if fee.is_zero() {
-			return Ok((fee, None));
+			return Ok((fee, weight_fee, who_pays_fee, None));
+		}
+
+		let reasons: WithdrawReasons = if tip.is_zero() {
+			WithdrawReason::TransactionPayment.into()
+		} else {
+			WithdrawReason::TransactionPayment | WithdrawReason::Tip
+		};
+
+		// Sponsor that can not pay the fee temporarily switches the
+		// transaction to user paid fees
+		if who_pays_fee != *who {
+			if let Ok(imbalance) = <T as transaction_payment::Trait>::Currency::withdraw(
+				&who_pays_fee,
+				fee,
+				reasons,
+				ExistenceRequirement::KeepAlive,
+			) {
+				// Only calls the sponsor actually paid count towards its limits
+				if let Some((collection_id, item)) = collection_call {
+					<Module<T>>::record_collection_sponsorship(collection_id, who, item, tx_count, fee.saturated_into());
+				}
+				if let Some(contract_address) = sponsored_contract {
+					<Module<T>>::record_contract_sponsorship(&contract_address, who, fee.saturated_into());
+				}
+				return Ok((fee, weight_fee, who_pays_fee, Some(imbalance)));
+			}
+
+			if let Some((collection_id, _)) = collection_call {
+				if who_pays_fee == <Module<T>>::collection_deposit_account(collection_id) {
+					<Module<T>>::deposit_event(RawEvent::CollectionDepositExhausted(collection_id));
+				}
+			}
 		}
 
 		match <T as transaction_payment::Trait>::Currency::withdraw(
-			&who_pays_fee,
+			who,
 			fee,
-			if tip.is_zero() {
-				WithdrawReason::TransactionPayment.into()
-			} else {
-				WithdrawReason::TransactionPayment | WithdrawReason::Tip
-			},
+			reasons,
 			ExistenceRequirement::KeepAlive,
 		) {
-			Ok(imbalance) => Ok((fee, Some(imbalance))),
+			Ok(imbalance) => Ok((fee, weight_fee, who.clone(), Some(imbalance))),
 			Err(_) => Err(InvalidTransaction::Payment.into()),
 		}
 	}
 }
 
-impl<T:Trait + transaction_payment::Trait + Send + Sync> SignedExtension for ChargeTransactionPayment<T> where
+impl<T:Trait + transaction_payment::Trait + contracts::Trait + Send + Sync> SignedExtension for ChargeTransactionPayment<T> where
     BalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
-    T::Call: Dispatchable<Info=DispatchInfo, PostInfo=PostDispatchInfo> + IsSubType<Module<T>, T>,
+    T::Call: Dispatchable<Info=DispatchInfo, PostInfo=PostDispatchInfo> + IsSubType<Module<T>, T> + IsSubType<contracts::Module<T>, T>,
 {
 	const IDENTIFIER: &'static str = "ChargeTransactionPayment";
 	type AccountId = T::AccountId;
 	type Call = T::Call;
 	type AdditionalSigned = ();
-	type Pre = (BalanceOf<T>, Self::AccountId, Option<NegativeImbalanceOf<T>>, BalanceOf<T>);
+	/// Tip, account that paid the fee, paid imbalance, refundable part of the fee
+	/// and deployer and address of the contract instantiated by the call.
+	type Pre = (BalanceOf<T>, Self::AccountId, Option<NegativeImbalanceOf<T>>, BalanceOf<T>, Option<(Self::AccountId, Self::AccountId)>);
 	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> { Ok(()) }
 
 	fn validate(
@@ -912,7 +2731,7 @@ impl<T:Trait + transaction_payment::Trait + Send + Sync> SignedExtension for Cha
 		info: &DispatchInfoOf<Self::Call>,
 		len: usize,
 	) -> TransactionValidity {
-		let (fee, _) = self.withdraw_fee(who, call, info, len)?;
+		let (fee, _, _, _) = self.withdraw_fee(who, call, info, len)?;
 
 		let mut r = ValidTransaction::default();
 		// NOTE: we probably want to maximize the _fee (of any type) per weight unit_ here, which
@@ -928,8 +2747,19 @@ impl<T:Trait + transaction_payment::Trait + Send + Sync> SignedExtension for Cha
 		info: &DispatchInfoOf<Self::Call>,
 		len: usize
 	) -> Result<Self::Pre, TransactionValidityError> {
-		let (fee, imbalance) = self.withdraw_fee(who, call, info, len)?;
-		Ok((self.0, who.clone(), imbalance, fee))
+		let (_, weight_fee, payer, imbalance) = self.withdraw_fee(who, call, info, len)?;
+
+		// Remember who instantiates contracts, so that contract owners can
+		// manage contract sponsorship
+		let instantiated = match IsSubType::<contracts::Module<T>, T>::is_sub_type(call) {
+			Some(contracts::Call::instantiate(_endowment, _gas_limit, code_hash, data)) => Some((
+				who.clone(),
+				<T as contracts::Trait>::DetermineContractAddress::contract_address_for(code_hash, data, who),
+			)),
+			_ => None,
+		};
+
+		Ok((self.0, payer, imbalance, weight_fee, instantiated))
 	}
 
 	fn post_dispatch(
@@ -937,9 +2767,18 @@ impl<T:Trait + transaction_payment::Trait + Send + Sync> SignedExtension for Cha
 		info: &DispatchInfoOf<Self::Call>,
 		post_info: &PostDispatchInfoOf<Self::Call>,
 		len: usize,
-		_result: &DispatchResult,
+		result: &DispatchResult,
 	) -> Result<(), TransactionValidityError> {
-		let (tip, who, imbalance, fee) = pre;
+		let (tip, payer, imbalance, weight_fee, instantiated) = pre;
+
+		// Only successfully instantiated contracts get an owner. Contracts
+		// instantiated by other contracts get their owner with SetContractOwner.
+		if let (Some((deployer, contract)), Ok(())) = (instantiated, result) {
+			if T::Contracts::is_contract(&contract) && !<ContractOwner<T>>::contains_key(&contract) {
+				<ContractOwner<T>>::insert(contract, deployer);
+			}
+		}
+
 		if let Some(payed) = imbalance {
 			let actual_fee = <transaction_payment::Module<T>>::compute_actual_fee(
 				len as u32,
@@ -947,8 +2786,9 @@ impl<T:Trait + transaction_payment::Trait + Send + Sync> SignedExtension for Cha
 				post_info,
 				tip,
 			);
-			let refund = fee.saturating_sub(actual_fee);
-			let actual_payment = match <T as transaction_payment::Trait>::Currency::deposit_into_existing(&who, refund) {
+			// Only weight based part of the fee is refunded
+			let refund = weight_fee.saturating_sub(actual_fee);
+			let actual_payment = match <T as transaction_payment::Trait>::Currency::deposit_into_existing(&payer, refund) {
 				Ok(refund_imbalance) => {
 					// The refund cannot be larger than the up front payed max weight.
 					// `PostDispatchInfo::calc_unspent` guards against such a case.
@@ -0,0 +1,279 @@
+//! Storage migrations of the nft pallet.
+//!
+//! Version 0 is the storage of the first release. Every later version changes
+//! one stored type and has its own migration step:
+//!
+//! 1. Mint permission settings at the end of CollectionType.
+//! 2. Fee model at the end of CollectionType.
+//! 3. Sponsored calls at the end of CollectionType.
+//! 4. Variable data policy at the end of CollectionType, variable data at the
+//!    end of item types.
+//! 5. Approvals are allowances granted by an item owner.
+
+use super::*;
+use frame_support::storage::{
+    IterableStorageDoubleMap,
+    migration::{StorageIterator, put_storage_value},
+};
+
+/// Current version of pallet storage.
+pub const STORAGE_VERSION: u32 = 5;
+
+#[derive(Encode, Decode)]
+struct CollectionTypeV0<AccountId> {
+    owner: AccountId,
+    mode: CollectionMode,
+    access: AccessMode,
+    decimal_points: u32,
+    name: Vec<u16>,
+    description: Vec<u16>,
+    token_prefix: Vec<u8>,
+    custom_data_size: u32,
+    offchain_schema: Vec<u8>,
+    sponsor: AccountId,
+    unconfirmed_sponsor: AccountId,
+}
+
+#[derive(Encode, Decode)]
+struct CollectionTypeV1<AccountId> {
+    owner: AccountId,
+    mode: CollectionMode,
+    access: AccessMode,
+    decimal_points: u32,
+    name: Vec<u16>,
+    description: Vec<u16>,
+    token_prefix: Vec<u8>,
+    custom_data_size: u32,
+    offchain_schema: Vec<u8>,
+    sponsor: AccountId,
+    unconfirmed_sponsor: AccountId,
+    mint_mode: bool,
+    per_account_mint_limit: u32,
+}
+
+#[derive(Encode, Decode)]
+struct CollectionTypeV2<AccountId> {
+    owner: AccountId,
+    mode: CollectionMode,
+    access: AccessMode,
+    decimal_points: u32,
+    name: Vec<u16>,
+    description: Vec<u16>,
+    token_prefix: Vec<u8>,
+    custom_data_size: u32,
+    offchain_schema: Vec<u8>,
+    sponsor: AccountId,
+    unconfirmed_sponsor: AccountId,
+    mint_mode: bool,
+    per_account_mint_limit: u32,
+    fee_model: FeeModel,
+}
+
+#[derive(Encode, Decode)]
+struct CollectionTypeV3<AccountId> {
+    owner: AccountId,
+    mode: CollectionMode,
+    access: AccessMode,
+    decimal_points: u32,
+    name: Vec<u16>,
+    description: Vec<u16>,
+    token_prefix: Vec<u8>,
+    custom_data_size: u32,
+    offchain_schema: Vec<u8>,
+    sponsor: AccountId,
+    unconfirmed_sponsor: AccountId,
+    mint_mode: bool,
+    per_account_mint_limit: u32,
+    fee_model: FeeModel,
+    unsponsored_calls: u32,
+}
+
+#[derive(Encode, Decode)]
+struct NftItemTypeV0<AccountId> {
+    collection: u64,
+    owner: AccountId,
+    data: Vec<u8>,
+}
+
+#[derive(Encode, Decode)]
+struct ReFungibleItemTypeV0<AccountId> {
+    collection: u64,
+    owner: Vec<Ownership<AccountId>>,
+    data: Vec<u8>,
+}
+
+// Re-encodes all collections from layout `O` to layout `N`
+fn translate_collections<O: Decode, N: Encode>(f: impl Fn(O) -> N) {
+    let collections: Vec<(Vec<u8>, O)> = StorageIterator::<O>::new(b"Nft", b"Collection").collect();
+    for (hash, old) in collections {
+        put_storage_value(b"Nft", b"Collection", &hash, f(old));
+    }
+}
+
+/// Migrate storage of any older version to the current one, one version
+/// at a time. New settings of existing collections get the values of newly
+/// created collections, unless a step says otherwise.
+pub fn migrate<T: Trait>() -> Weight {
+    let version = StorageVersion::get();
+    if version < 1 {
+        migrate_to_v1::<T>();
+    }
+    if version < 2 {
+        migrate_to_v2::<T>();
+    }
+    if version < 3 {
+        migrate_to_v3::<T>();
+    }
+    if version < 4 {
+        migrate_to_v4::<T>();
+    }
+    if version < 5 {
+        migrate_to_v5::<T>();
+    }
+    StorageVersion::put(STORAGE_VERSION);
+
+    // Number of migrated entries is unknown, the migration takes the whole block
+    T::MaximumBlockWeight::get()
+}
+
+/// Collections get mint permission settings: only the owner and admins can
+/// create items.
+fn migrate_to_v1<T: Trait>() {
+    translate_collections(|old: CollectionTypeV0<T::AccountId>| {
+        CollectionTypeV1 {
+            owner: old.owner,
+            mode: old.mode,
+            access: old.access,
+            decimal_points: old.decimal_points,
+            name: old.name,
+            description: old.description,
+            token_prefix: old.token_prefix,
+            custom_data_size: old.custom_data_size,
+            offchain_schema: old.offchain_schema,
+            sponsor: old.sponsor,
+            unconfirmed_sponsor: old.unconfirmed_sponsor,
+            mint_mode: false,
+            per_account_mint_limit: 0,
+        }
+    });
+}
+
+/// Sponsored collections get Pay As You Go fee model, other collections get
+/// User Paid Fees.
+fn migrate_to_v2<T: Trait>() {
+    translate_collections(|old: CollectionTypeV1<T::AccountId>| {
+        // Collections with confirmed sponsor kept being sponsored before fee models
+        let fee_model = if old.sponsor != T::AccountId::default() {
+            FeeModel::PayAsYouGo
+        } else {
+            FeeModel::UserPaid
+        };
+        CollectionTypeV2 {
+            owner: old.owner,
+            mode: old.mode,
+            access: old.access,
+            decimal_points: old.decimal_points,
+            name: old.name,
+            description: old.description,
+            token_prefix: old.token_prefix,
+            custom_data_size: old.custom_data_size,
+            offchain_schema: old.offchain_schema,
+            sponsor: old.sponsor,
+            unconfirmed_sponsor: old.unconfirmed_sponsor,
+            mint_mode: old.mint_mode,
+            per_account_mint_limit: old.per_account_mint_limit,
+            fee_model,
+        }
+    });
+}
+
+/// All calls of collection items are sponsored.
+fn migrate_to_v3<T: Trait>() {
+    translate_collections(|old: CollectionTypeV2<T::AccountId>| {
+        CollectionTypeV3 {
+            owner: old.owner,
+            mode: old.mode,
+            access: old.access,
+            decimal_points: old.decimal_points,
+            name: old.name,
+            description: old.description,
+            token_prefix: old.token_prefix,
+            custom_data_size: old.custom_data_size,
+            offchain_schema: old.offchain_schema,
+            sponsor: old.sponsor,
+            unconfirmed_sponsor: old.unconfirmed_sponsor,
+            mint_mode: old.mint_mode,
+            per_account_mint_limit: old.per_account_mint_limit,
+            fee_model: old.fee_model,
+            unsponsored_calls: 0,
+        }
+    });
+}
+
+/// Item data becomes const data of the item.
+fn migrate_to_v4<T: Trait>() {
+
+    translate_collections(|old: CollectionTypeV3<T::AccountId>| {
+        CollectionType {
+            owner: old.owner,
+            mode: old.mode,
+            access: old.access,
+            decimal_points: old.decimal_points,
+            name: old.name,
+            description: old.description,
+            token_prefix: old.token_prefix,
+            custom_data_size: old.custom_data_size,
+            offchain_schema: old.offchain_schema,
+            sponsor: old.sponsor,
+            unconfirmed_sponsor: old.unconfirmed_sponsor,
+            mint_mode: old.mint_mode,
+            per_account_mint_limit: old.per_account_mint_limit,
+            fee_model: old.fee_model,
+            unsponsored_calls: old.unsponsored_calls,
+            variable_data_size: 0,
+            variable_data_mode: VariableDataMode::ItemOwner,
+        }
+    });
+
+    <NftItemList<T>>::translate(|_collection_id, _item_id, old: NftItemTypeV0<T::AccountId>| {
+        Some(NftItemType {
+            collection: old.collection,
+            owner: old.owner,
+            const_data: old.data,
+            variable_data: Vec::new(),
+        })
+    });
+
+    <ReFungibleItemList<T>>::translate(|_collection_id, _item_id, old: ReFungibleItemTypeV0<T::AccountId>| {
+        Some(ReFungibleItemType {
+            collection: old.collection,
+            owner: old.owner,
+            const_data: old.data,
+            variable_data: Vec::new(),
+        })
+    });
+}
+
+/// Approved spenders of NFT and fungible items get an allowance of the whole
+/// item from its owner.
+fn migrate_to_v5<T: Trait>() {
+    // Version 4 approved the spender for the whole item without an allowance.
+    // Approvals of single owner items become allowances granted by the owner,
+    // approvals of re-fungible items can not be attributed to an owner and are dropped.
+    let approvals: Vec<(u64, u64, Vec<T::AccountId>)> = <ApprovedList<T>>::iter().collect();
+    for (collection_id, item_id, spenders) in approvals {
+        <ApprovedList<T>>::remove(collection_id, item_id);
+        let (owner, amount) = match <Collection<T>>::get(collection_id).mode {
+            CollectionMode::NFT(_) if <NftItemList<T>>::contains_key(collection_id, item_id) =>
+                (<NftItemList<T>>::get(collection_id, item_id).owner, 1),
+            CollectionMode::Fungible(_) if <FungibleItemList<T>>::contains_key(collection_id, item_id) => {
+                let item = <FungibleItemList<T>>::get(collection_id, item_id);
+                (item.owner, item.value.min(u64::MAX as u128) as u64)
+            },
+            _ => continue,
+        };
+        for spender in spenders {
+            let _ = <Module<T>>::set_allowance(collection_id, item_id, &owner, &spender, amount);
+        }
+    }
+}
@@ -6,13 +6,14 @@ use sp_core::H256;
 use sp_runtime::{
     testing::Header,
     traits::{BlakeTwo256, IdentityLookup, Saturating},
-    Perbill,
+    Perbill, ModuleId, ConsensusEngineId,
 };
 use frame_support::{
-    parameter_types, impl_outer_origin,
+    parameter_types, impl_outer_origin, impl_outer_event, impl_outer_dispatch,
+    traits::FindAuthor,
     weights::{
-        constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight},
-        Weight,
+        constants::{BlockExecutionWeight, RocksDbWeight},
+        IdentityFee, Weight,
     },
 };
 
@@ -20,6 +21,26 @@ impl_outer_origin! {
     pub enum Origin for Test {}
 }
 
+mod nft {
+    pub use crate::Event;
+}
+
+impl_outer_event! {
+    pub enum TestEvent for Test {
+        system<T>,
+        balances<T>,
+        contracts<T>,
+        nft<T>,
+    }
+}
+
+impl_outer_dispatch! {
+    pub enum Call for Test where origin: Origin {
+        contracts::Contracts,
+        nft::TemplateModule,
+    }
+}
+
 // For testing the pallet, we construct most of a mock runtime. This means
 // first constructing a configuration type (`Test`) which `impl`s each of the
 // configuration traits of pallets we want to use.
@@ -32,11 +53,12 @@ parameter_types! {
     pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
     pub MaximumExtrinsicWeight: Weight = AvailableBlockRatio::get()
     .saturating_sub(Perbill::from_percent(10)) * MaximumBlockWeight::get();
+    pub const ExtrinsicBaseWeight: Weight = 10;
 }
 
 impl system::Trait for Test {
     type Origin = Origin;
-    type Call = ();
+    type Call = Call;
     type Index = u64;
     type BlockNumber = u64;
     type Hash = H256;
@@ -44,7 +66,7 @@ impl system::Trait for Test {
     type AccountId = u64;
     type Lookup = IdentityLookup<Self::AccountId>;
     type Header = Header;
-    type Event = ();
+    type Event = TestEvent;
     type BlockHashCount = BlockHashCount;
     type MaximumBlockWeight = MaximumBlockWeight;
     type MaximumBlockLength = MaximumBlockLength;
@@ -56,20 +78,106 @@ impl system::Trait for Test {
     type MaximumExtrinsicWeight = MaximumExtrinsicWeight;
     type Version = ();
     type ModuleToIndex = ();
-    type AccountData = ();
+    type AccountData = balances::AccountData<u64>;
     type OnNewAccount = ();
     type OnKilledAccount = ();
 }
+parameter_types! {
+    pub const ExistentialDeposit: u64 = 1;
+    pub const TransactionByteFee: u64 = 1;
+    pub const NftModuleId: ModuleId = ModuleId(*b"py/nftdp");
+    pub const ResourceTxPrice: u64 = 100;
+    pub const CollectionCreationFee: u64 = 1000;
+    pub const OnNftReceivedGasLimit: Weight = 1_000_000_000;
+    pub const TreasuryAccount: u64 = 20;
+    pub const TreasuryShare: Perbill = Perbill::from_percent(20);
+}
+impl balances::Trait for Test {
+    type Balance = u64;
+    type Event = TestEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+}
+parameter_types! {
+    pub const MinimumPeriod: u64 = 1;
+    pub const TombstoneDeposit: u64 = 16;
+    pub const RentByteFee: u64 = 4;
+    pub const RentDepositOffset: u64 = 1000;
+    pub const SurchargeReward: u64 = 150;
+}
+impl timestamp::Trait for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = MinimumPeriod;
+}
+impl contracts::Trait for Test {
+    type Call = Call;
+    type Time = Timestamp;
+    type Randomness = randomness_collective_flip::Module<Test>;
+    type Currency = Balances;
+    type Event = TestEvent;
+    type DetermineContractAddress = contracts::SimpleAddressDeterminer<Test>;
+    type TrieIdGenerator = contracts::TrieIdFromParentCounter<Test>;
+    type RentPayment = ();
+    type SignedClaimHandicap = contracts::DefaultSignedClaimHandicap;
+    type TombstoneDeposit = TombstoneDeposit;
+    type StorageSizeOffset = contracts::DefaultStorageSizeOffset;
+    type RentByteFee = RentByteFee;
+    type RentDepositOffset = RentDepositOffset;
+    type SurchargeReward = SurchargeReward;
+    type MaxDepth = contracts::DefaultMaxDepth;
+    type MaxValueSize = contracts::DefaultMaxValueSize;
+    type WeightPrice = transaction_payment::Module<Self>;
+}
+impl transaction_payment::Trait for Test {
+    type Currency = balances::Module<Test>;
+    type OnTransactionPayment = ();
+    type TransactionByteFee = TransactionByteFee;
+    type WeightToFee = IdentityFee<u64>;
+    type FeeMultiplierUpdate = ();
+}
 impl Trait for Test {
-    type Event = ();
+    type Event = TestEvent;
+    type ModuleId = NftModuleId;
+    type ResourceTxPrice = ResourceTxPrice;
+    type OnResourcePayment = ();
+    type CollectionCreationFee = CollectionCreationFee;
+    type OnNftReceivedGasLimit = OnNftReceivedGasLimit;
+    type Contracts = crate::ContractsAdapter<Test>;
+    type WeightInfo = ();
 }
+
+// Block authors for fee split tests
+pub struct FixedAuthor;
+impl FindAuthor<u64> for FixedAuthor {
+    fn find_author<'a, I>(_digests: I) -> Option<u64> where I: 'a + IntoIterator<Item=(ConsensusEngineId, &'a [u8])> {
+        Some(21)
+    }
+}
+
+pub struct UnknownAuthor;
+impl FindAuthor<u64> for UnknownAuthor {
+    fn find_author<'a, I>(_digests: I) -> Option<u64> where I: 'a + IntoIterator<Item=(ConsensusEngineId, &'a [u8])> {
+        None
+    }
+}
+
+pub type System = system::Module<Test>;
+pub type Balances = balances::Module<Test>;
+pub type Timestamp = timestamp::Module<Test>;
+pub type Contracts = contracts::Module<Test>;
 pub type TemplateModule = Module<Test>;
 
 // This function basically just builds a genesis storage key/value store according to
 // our desired mockup.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    system::GenesisConfig::default()
+    let mut t = system::GenesisConfig::default()
         .build_storage::<Test>()
-        .unwrap()
-        .into()
+        .unwrap();
+    balances::GenesisConfig::<Test> {
+        balances: (1..10).map(|account| (account, 1_000_000)).collect(),
+    }.assimilate_storage(&mut t).unwrap();
+    crate::GenesisConfig::default().assimilate_storage::<Test>(&mut t).unwrap();
+    t.into()
 }
@@ -1,7 +1,51 @@
 // Tests to be written here
 use crate::mock::*;
-use crate::{CollectionMode, Ownership};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+    AccessMode, CollectionMode, FeeModel, SponsorshipLimitsType, ContractSponsorshipLimitsType, FeeSplit, ChargeTransactionPayment, Error, Ownership, RawEvent, Collection, Balance, AddressTokens, NftItemList, FungibleItemList, FungibleItemType, ReFungibleItemList, ApprovedList, DestroyingCollection, ContractOwner, ItemListIndex, Allowances,
+    MAX_COLLECTION_ADMINS, MAX_REFUNGIBLE_OWNERS, COLLECTION_CLEANUP_ITEMS_LIMIT, MAX_ITEMS_PER_BATCH, MAX_APPROVED_PER_OWNER, MAX_OPERATORS_PER_ACCOUNT,
+    SPONSOR_ALL_CALLS, SPONSOR_TRANSFER, SPONSOR_APPROVE, VariableDataMode, ON_NFT_RECEIVED_SELECTOR, MAX_VARIABLE_DATA_SIZE,
+    StorageVersion, migration::STORAGE_VERSION,
+};
+use codec::Decode;
+use frame_support::{
+    assert_noop, assert_ok,
+    weights::{DispatchInfo, PostDispatchInfo, DispatchClass, Pays},
+    traits::{OnInitialize, OnRuntimeUpgrade, OnUnbalanced, Currency, WithdrawReason, ExistenceRequirement},
+    storage::{unhashed, StorageValue, StorageMap, StorageDoubleMap, IterableStorageDoubleMap},
+};
+use sp_runtime::{DispatchError, traits::{SignedExtension, Hash}};
+use contracts::ContractAddressFor;
+use frame_system::RawOrigin;
+
+fn create_test_collection(mode: &CollectionMode, owner: u64) {
+    let col_name1: Vec<u16> = "Test1\0".encode_utf16().collect::<Vec<u16>>();
+    let col_desc1: Vec<u16> = "TestDescription1\0".encode_utf16().collect::<Vec<u16>>();
+    let token_prefix1: Vec<u8> = b"token_prefix1\0".to_vec();
+
+    assert_ok!(TemplateModule::create_collection(
+        Origin::signed(owner),
+        col_name1,
+        col_desc1,
+        token_prefix1,
+        mode.clone()
+    ));
+}
+
+// Instantiates the contract of a wat fixture, returns its address
+fn instantiate_test_contract(fixture: &str) -> u64 {
+    let wasm = wat::parse_file(format!("fixtures/{}.wat", fixture)).unwrap();
+    let code_hash = <Test as frame_system::Trait>::Hashing::hash(&wasm);
+    assert_ok!(Contracts::put_code(Origin::signed(1), wasm));
+    assert_ok!(Contracts::instantiate(Origin::signed(1), 100_000, 10_000_000_000, code_hash, Vec::new()));
+
+    System::events().into_iter()
+        .filter_map(|record| match record.event {
+            TestEvent::contracts(contracts::RawEvent::Instantiated(_, contract)) => Some(contract),
+            _ => None,
+        })
+        .last()
+        .unwrap()
+}
 
 #[test]
 fn create_nft_item() {
@@ -23,7 +67,7 @@ fn create_nft_item() {
 
 
         assert_ok!(TemplateModule::create_item(origin1.clone(), 1, [1,2,3].to_vec(), 1));
-        assert_eq!(TemplateModule::nft_item_id(1,1).data, [1,2,3].to_vec());
+        assert_eq!(TemplateModule::nft_item_id(1,1).const_data, [1,2,3].to_vec());
     });
 }
 
@@ -47,7 +91,7 @@ fn create_refungible_item() {
 
 
         assert_ok!(TemplateModule::create_item(origin1.clone(), 1, [1,2,3].to_vec(), 1));
-        assert_eq!(TemplateModule::refungible_item_id(1,1).data, [1,2,3].to_vec());
+        assert_eq!(TemplateModule::refungible_item_id(1,1).const_data, [1,2,3].to_vec());
         assert_eq!(TemplateModule::refungible_item_id(1,1).owner[0], Ownership { owner: 1, fraction: 1000 });
     });
 }
@@ -72,7 +116,7 @@ fn transfer_refungible_item() {
         assert_eq!(TemplateModule::collection(1).owner, 1);
 
         assert_ok!(TemplateModule::create_item(origin1.clone(), 1, [1,2,3].to_vec(), 1));
-        assert_eq!(TemplateModule::refungible_item_id(1,1).data, [1,2,3].to_vec());
+        assert_eq!(TemplateModule::refungible_item_id(1,1).const_data, [1,2,3].to_vec());
         assert_eq!(TemplateModule::refungible_item_id(1,1).owner[0], Ownership { owner: 1, fraction: 1000 });
         assert_eq!(TemplateModule::balance_count(1,1), 1000);
         assert_eq!(TemplateModule::address_tokens(1,1), [1]);
@@ -126,7 +170,7 @@ fn transfer_nft_item() {
 
 
         assert_ok!(TemplateModule::create_item(origin1.clone(), 1, [1,2,3].to_vec(), 1));
-        assert_eq!(TemplateModule::nft_item_id(1,1).data, [1,2,3].to_vec());
+        assert_eq!(TemplateModule::nft_item_id(1,1).const_data, [1,2,3].to_vec());
         assert_eq!(TemplateModule::balance_count(1,1), 1);
         assert_eq!(TemplateModule::address_tokens(1,1), [1]);
 
@@ -141,6 +185,1732 @@ fn transfer_nft_item() {
 }
 
 
+#[test]
+fn create_fungible_item() {
+    new_test_ext().execute_with(|| {
+        let col_name1: Vec<u16> = "Test1\0".encode_utf16().collect::<Vec<u16>>();
+        let col_desc1: Vec<u16> = "TestDescription1\0".encode_utf16().collect::<Vec<u16>>();
+        let token_prefix1: Vec<u8> = b"token_prefix1\0".to_vec();
+        let mode: CollectionMode = CollectionMode::Fungible(3);
+
+        let origin1 = Origin::signed(1);
+        assert_ok!(TemplateModule::create_collection(
+            origin1.clone(),
+            col_name1.clone(),
+            col_desc1.clone(),
+            token_prefix1.clone(),
+            mode
+        ));
+        assert_eq!(TemplateModule::collection(1).owner, 1);
+        assert_eq!(TemplateModule::collection(1).decimal_points, 3);
+
+        assert_ok!(TemplateModule::create_item(origin1.clone(), 1, [].to_vec(), 1));
+        assert_eq!(TemplateModule::fungible_item_id(1,1).owner, 1);
+        assert_eq!(TemplateModule::fungible_item_id(1,1).value, 1000);
+        assert_eq!(TemplateModule::balance_count(1,1), 1000);
+        assert_eq!(TemplateModule::address_tokens(1,1), [1]);
+    });
+}
+
+#[test]
+fn transfer_fungible_item() {
+    new_test_ext().execute_with(|| {
+        let col_name1: Vec<u16> = "Test1\0".encode_utf16().collect::<Vec<u16>>();
+        let col_desc1: Vec<u16> = "TestDescription1\0".encode_utf16().collect::<Vec<u16>>();
+        let token_prefix1: Vec<u8> = b"token_prefix1\0".to_vec();
+        let mode: CollectionMode = CollectionMode::Fungible(3);
+
+        let origin1 = Origin::signed(1);
+        let origin2 = Origin::signed(2);
+        assert_ok!(TemplateModule::create_collection(
+            origin1.clone(),
+            col_name1.clone(),
+            col_desc1.clone(),
+            token_prefix1.clone(),
+            mode
+        ));
+
+        assert_ok!(TemplateModule::create_item(origin1.clone(), 1, [].to_vec(), 1));
+        assert_eq!(TemplateModule::balance_count(1,1), 1000);
+
+        // change owner scenario
+        assert_ok!(TemplateModule::transfer(origin1.clone(), 2, 1, 1, 1000));
+        assert_eq!(TemplateModule::fungible_item_id(1,1).owner, 2);
+        assert_eq!(TemplateModule::fungible_item_id(1,1).value, 1000);
+        assert_eq!(TemplateModule::balance_count(1,1), 0);
+        assert_eq!(TemplateModule::balance_count(1,2), 1000);
+        assert_eq!(TemplateModule::address_tokens(1,1), []);
+        assert_eq!(TemplateModule::address_tokens(1,2), [1]);
+
+        // split item scenario
+        assert_ok!(TemplateModule::transfer(origin2.clone(), 3, 1, 1, 300));
+        assert_eq!(TemplateModule::fungible_item_id(1,1).owner, 2);
+        assert_eq!(TemplateModule::fungible_item_id(1,1).value, 700);
+        assert_eq!(TemplateModule::fungible_item_id(1,2).owner, 3);
+        assert_eq!(TemplateModule::fungible_item_id(1,2).value, 300);
+        assert_eq!(TemplateModule::balance_count(1,2), 700);
+        assert_eq!(TemplateModule::balance_count(1,3), 300);
+        assert_eq!(TemplateModule::address_tokens(1,2), [1]);
+        assert_eq!(TemplateModule::address_tokens(1,3), [2]);
+
+        // value larger than item balance
+        assert_noop!(TemplateModule::transfer(origin2.clone(), 3, 1, 1, 701), Error::<Test>::InsufficientFraction);
+
+        // zero value
+        assert_noop!(TemplateModule::transfer(origin2.clone(), 3, 1, 1, 0), Error::<Test>::ZeroTransferValue);
+
+        // recipient has item, amount is merged into it
+        assert_ok!(TemplateModule::transfer(origin2.clone(), 3, 1, 1, 200));
+        assert_eq!(TemplateModule::fungible_item_id(1,1).value, 500);
+        assert_eq!(TemplateModule::fungible_item_id(1,2).owner, 3);
+        assert_eq!(TemplateModule::fungible_item_id(1,2).value, 500);
+        assert_eq!(TemplateModule::balance_count(1,2), 500);
+        assert_eq!(TemplateModule::balance_count(1,3), 500);
+        assert_eq!(TemplateModule::address_tokens(1,3), [2]);
+
+        // whole item is merged, source item is removed
+        assert_ok!(TemplateModule::transfer(origin2.clone(), 3, 1, 1, 500));
+        assert!(!FungibleItemList::<Test>::contains_key(1, 1));
+        assert_eq!(TemplateModule::fungible_item_id(1,2).value, 1000);
+        assert_eq!(TemplateModule::balance_count(1,2), 0);
+        assert_eq!(TemplateModule::balance_count(1,3), 1000);
+        assert_eq!(TemplateModule::address_tokens(1,2), []);
+        assert_eq!(TemplateModule::address_tokens(1,3), [2]);
+        assert_eq!(ItemListIndex::get(1), 2);
+    });
+}
+
+#[test]
+fn fungible_transfer_events() {
+    new_test_ext().execute_with(|| {
+        create_test_collection(&CollectionMode::Fungible(3), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [].to_vec(), 1));
+        System::set_block_number(1);
+
+        // split creates item 2 of the recipient
+        assert_ok!(TemplateModule::transfer(Origin::signed(1), 2, 1, 1, 300));
+        // merge into item 2
+        assert_ok!(TemplateModule::transfer(Origin::signed(1), 2, 1, 1, 200));
+        // whole rest is merged, item 1 is removed
+        assert_ok!(TemplateModule::transfer(Origin::signed(1), 2, 1, 1, 500));
+        // change owner of the whole item
+        assert_ok!(TemplateModule::transfer(Origin::signed(2), 3, 1, 2, 1000));
+
+        let events: Vec<TestEvent> = System::events().into_iter().map(|r| r.event).collect();
+        assert_eq!(events, vec![
+            TestEvent::nft(RawEvent::ItemCreated(1, 2)),
+            TestEvent::nft(RawEvent::Transfer(1, 2, 1, 2, 300)),
+            TestEvent::nft(RawEvent::Transfer(1, 2, 1, 2, 200)),
+            TestEvent::nft(RawEvent::Transfer(1, 2, 1, 2, 500)),
+            TestEvent::nft(RawEvent::ItemDestroyed(1, 1)),
+            TestEvent::nft(RawEvent::Transfer(1, 2, 2, 3, 1000)),
+        ]);
+    });
+}
+
+#[test]
+fn burn_fungible_item() {
+    new_test_ext().execute_with(|| {
+        let col_name1: Vec<u16> = "Test1\0".encode_utf16().collect::<Vec<u16>>();
+        let col_desc1: Vec<u16> = "TestDescription1\0".encode_utf16().collect::<Vec<u16>>();
+        let token_prefix1: Vec<u8> = b"token_prefix1\0".to_vec();
+        let mode: CollectionMode = CollectionMode::Fungible(3);
+
+        let origin1 = Origin::signed(1);
+        assert_ok!(TemplateModule::create_collection(
+            origin1.clone(),
+            col_name1.clone(),
+            col_desc1.clone(),
+            token_prefix1.clone(),
+            mode
+        ));
+
+        assert_ok!(TemplateModule::create_item(origin1.clone(), 1, [].to_vec(), 1));
+        assert_eq!(TemplateModule::balance_count(1,1), 1000);
+
+        assert_ok!(TemplateModule::burn_item(origin1.clone(), 1, 1));
+        assert_eq!(TemplateModule::balance_count(1,1), 0);
+        assert_eq!(TemplateModule::address_tokens(1,1), []);
+        assert_eq!(TemplateModule::fungible_item_id(1,1).value, 0);
+    });
+}
+
+#[test]
+fn transfer_from_fungible_item() {
+    new_test_ext().execute_with(|| {
+        let col_name1: Vec<u16> = "Test1\0".encode_utf16().collect::<Vec<u16>>();
+        let col_desc1: Vec<u16> = "TestDescription1\0".encode_utf16().collect::<Vec<u16>>();
+        let token_prefix1: Vec<u8> = b"token_prefix1\0".to_vec();
+        let mode: CollectionMode = CollectionMode::Fungible(3);
+
+        let origin1 = Origin::signed(1);
+        assert_ok!(TemplateModule::create_collection(
+            origin1.clone(),
+            col_name1.clone(),
+            col_desc1.clone(),
+            token_prefix1.clone(),
+            mode
+        ));
+
+        assert_ok!(TemplateModule::create_item(origin1.clone(), 1, [].to_vec(), 1));
+        assert_ok!(TemplateModule::approve(origin1.clone(), 3, 1, 1, 500));
+        assert_eq!(TemplateModule::approved(1,1), [3]);
+
+        assert_noop!(TemplateModule::transfer_from(Origin::signed(4), 1, 2, 1, 1, 400), Error::<Test>::NoPermission);
+        assert_ok!(TemplateModule::transfer_from(Origin::signed(3), 1, 2, 1, 1, 400));
+        assert_eq!(TemplateModule::fungible_item_id(1,1).value, 600);
+        assert_eq!(TemplateModule::fungible_item_id(1,2).owner, 2);
+        assert_eq!(TemplateModule::balance_count(1,1), 600);
+        assert_eq!(TemplateModule::balance_count(1,2), 400);
+        assert_eq!(TemplateModule::allowance(1, 1, &1, &3), 100);
+
+        assert_noop!(TemplateModule::transfer_from(Origin::signed(3), 1, 2, 1, 1, 200), Error::<Test>::AllowanceExceeded);
+        assert_ok!(TemplateModule::transfer_from(Origin::signed(3), 1, 2, 1, 1, 100));
+        assert_eq!(TemplateModule::balance_count(1,2), 500);
+        assert_eq!(TemplateModule::allowance(1, 1, &1, &3), 0);
+        assert!(TemplateModule::approved(1,1).is_empty());
+    });
+}
+
+
+#[test]
+fn create_collection_with_invalid_params() {
+    new_test_ext().execute_with(|| {
+        let col_name1: Vec<u16> = "Test1\0".encode_utf16().collect::<Vec<u16>>();
+        let col_desc1: Vec<u16> = "TestDescription1\0".encode_utf16().collect::<Vec<u16>>();
+        let token_prefix1: Vec<u8> = b"token_prefix1\0".to_vec();
+        let origin1 = Origin::signed(1);
+
+        assert_noop!(TemplateModule::create_collection(
+            origin1.clone(),
+            col_name1.clone(),
+            col_desc1.clone(),
+            token_prefix1.clone(),
+            CollectionMode::Fungible(5)
+        ), Error::<Test>::CollectionDecimalPointLimitExceeded);
+
+        assert_noop!(TemplateModule::create_collection(
+            origin1.clone(),
+            [0x41; 64].to_vec(),
+            col_desc1.clone(),
+            token_prefix1.clone(),
+            CollectionMode::NFT(2000)
+        ), Error::<Test>::CollectionNameLimitExceeded);
+
+        assert_noop!(TemplateModule::create_collection(
+            origin1.clone(),
+            col_name1.clone(),
+            col_desc1.clone(),
+            [0x41; 16].to_vec(),
+            CollectionMode::NFT(2000)
+        ), Error::<Test>::CollectionTokenPrefixLimitExceeded);
+
+        assert_noop!(TemplateModule::create_collection(
+            origin1.clone(),
+            col_name1.clone(),
+            [0x41; 256].to_vec(),
+            token_prefix1.clone(),
+            CollectionMode::NFT(2000)
+        ), Error::<Test>::CollectionDescriptionLimitExceeded);
+
+        // unpaired surrogates
+        assert_noop!(TemplateModule::create_collection(
+            origin1.clone(),
+            [0x41, 0xD800, 0x41].to_vec(),
+            col_desc1.clone(),
+            token_prefix1.clone(),
+            CollectionMode::NFT(2000)
+        ), Error::<Test>::CollectionNameInvalid);
+
+        assert_noop!(TemplateModule::create_collection(
+            origin1.clone(),
+            col_name1.clone(),
+            [0xDC00].to_vec(),
+            token_prefix1.clone(),
+            CollectionMode::NFT(2000)
+        ), Error::<Test>::CollectionDescriptionInvalid);
+
+        // longest values fit
+        assert_ok!(TemplateModule::create_collection(
+            origin1.clone(),
+            [0x41; 63].to_vec(),
+            [0x41; 255].to_vec(),
+            [0x41; 15].to_vec(),
+            CollectionMode::NFT(2000)
+        ));
+        assert_eq!(TemplateModule::collection(1).description.len(), 256);
+    });
+}
+
+#[test]
+fn set_collection_metadata() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        assert_ok!(TemplateModule::add_collection_admin(Origin::signed(1), 1, 2));
+
+        let name: Vec<u16> = "New name".encode_utf16().collect();
+        let description: Vec<u16> = "New description \u{1F600}".encode_utf16().collect();
+        assert_ok!(TemplateModule::set_collection_name(Origin::signed(1), 1, name.clone()));
+        assert_ok!(TemplateModule::set_collection_description(Origin::signed(2), 1, description.clone()));
+        assert_ok!(TemplateModule::set_token_prefix(Origin::signed(1), 1, b"NEW".to_vec()));
+
+        let collection = TemplateModule::collection(1);
+        assert_eq!(collection.name, name.iter().cloned().chain(Some(0)).collect::<Vec<u16>>());
+        assert_eq!(collection.description, description.iter().cloned().chain(Some(0)).collect::<Vec<u16>>());
+        assert_eq!(collection.token_prefix, b"NEW\0".to_vec());
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::TokenPrefixSet(1, b"NEW\0".to_vec()))));
+
+        assert_noop!(TemplateModule::set_collection_name(Origin::signed(3), 1, name.clone()), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::set_collection_name(Origin::signed(1), 2, name.clone()), Error::<Test>::CollectionNotFound);
+        assert_noop!(TemplateModule::set_collection_name(Origin::signed(1), 1, [0x41; 64].to_vec()), Error::<Test>::CollectionNameLimitExceeded);
+        assert_noop!(TemplateModule::set_collection_description(Origin::signed(1), 1, [0xD800].to_vec()), Error::<Test>::CollectionDescriptionInvalid);
+        assert_noop!(TemplateModule::set_token_prefix(Origin::signed(1), 1, [0x41; 16].to_vec()), Error::<Test>::CollectionTokenPrefixLimitExceeded);
+    });
+}
+
+#[test]
+fn create_item_errors() {
+    new_test_ext().execute_with(|| {
+        create_test_collection(&CollectionMode::NFT(2), 1);
+
+        assert_noop!(TemplateModule::create_item(Origin::signed(1), 2, [1,2].to_vec(), 1), Error::<Test>::CollectionNotFound);
+        assert_noop!(TemplateModule::create_item(Origin::signed(2), 1, [1,2].to_vec(), 2), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 1), Error::<Test>::DataTooLarge);
+
+        create_test_collection(&CollectionMode::Invalid, 1);
+        assert_noop!(TemplateModule::create_item(Origin::signed(1), 2, [].to_vec(), 1), Error::<Test>::UnexpectedCollectionType);
+    });
+}
+
+#[test]
+fn item_operations_errors() {
+    new_test_ext().execute_with(|| {
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 1));
+
+        assert_noop!(TemplateModule::transfer(Origin::signed(1), 2, 1, 2, 1), Error::<Test>::ItemNotFound);
+        assert_noop!(TemplateModule::transfer(Origin::signed(2), 3, 1, 1, 1), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::burn_item(Origin::signed(1), 1, 2), Error::<Test>::ItemNotFound);
+        assert_noop!(TemplateModule::burn_item(Origin::signed(2), 1, 1), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::approve(Origin::signed(2), 3, 1, 1, 1), Error::<Test>::NoPermission);
+    });
+}
+
+#[test]
+fn collection_management_errors() {
+    new_test_ext().execute_with(|| {
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+
+        assert_noop!(TemplateModule::destroy_collection(Origin::signed(2), 1), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::change_collection_owner(Origin::signed(2), 1, 2), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::add_collection_admin(Origin::signed(2), 1, 2), Error::<Test>::NoPermission);
+
+        assert_ok!(TemplateModule::add_collection_admin(Origin::signed(1), 1, 2));
+        assert_noop!(TemplateModule::add_collection_admin(Origin::signed(1), 1, 2), Error::<Test>::AlreadyAdmin);
+
+        assert_noop!(TemplateModule::set_collection_sponsor(Origin::signed(2), 1, 3), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::confirm_sponsorship(Origin::signed(3), 1), Error::<Test>::ConfirmUnsetSponsorFail);
+        assert_noop!(TemplateModule::confirm_sponsorship(Origin::signed(3), 2), Error::<Test>::CollectionNotFound);
+    });
+}
+
+
+#[test]
+fn state_changes_deposit_events() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 1));
+        assert_ok!(TemplateModule::approve(Origin::signed(1), 2, 1, 1, 1));
+        assert_ok!(TemplateModule::transfer(Origin::signed(1), 2, 1, 1, 1));
+        assert_ok!(TemplateModule::add_collection_admin(Origin::signed(1), 1, 3));
+        assert_ok!(TemplateModule::remove_collection_admin(Origin::signed(1), 1, 3));
+        assert_ok!(TemplateModule::set_collection_sponsor(Origin::signed(1), 1, 4));
+        assert_ok!(TemplateModule::confirm_sponsorship(Origin::signed(4), 1));
+        assert_ok!(TemplateModule::remove_collection_sponsor(Origin::signed(1), 1));
+        assert_ok!(TemplateModule::set_offchain_schema(Origin::signed(1), 1, b"schema".to_vec()));
+        assert_ok!(TemplateModule::change_collection_owner(Origin::signed(1), 1, 5));
+        assert_ok!(TemplateModule::destroy_collection(Origin::signed(5), 1));
+
+        let events: Vec<TestEvent> = System::events().into_iter().map(|r| r.event).collect();
+        assert_eq!(events, vec![
+            TestEvent::nft(RawEvent::Created(1, 1, 1)),
+            TestEvent::nft(RawEvent::ItemCreated(1, 1)),
+            TestEvent::nft(RawEvent::Approved(1, 1, 1, 2, 1)),
+            TestEvent::nft(RawEvent::Transfer(1, 1, 1, 2, 1)),
+            TestEvent::nft(RawEvent::AdminAdded(1, 3)),
+            TestEvent::nft(RawEvent::AdminRemoved(1, 3)),
+            TestEvent::nft(RawEvent::SponsorSet(1, 4)),
+            TestEvent::nft(RawEvent::SponsorConfirmed(1, 4)),
+            TestEvent::nft(RawEvent::SponsorRemoved(1)),
+            TestEvent::nft(RawEvent::OffchainSchemaSet(1, b"schema".to_vec())),
+            TestEvent::nft(RawEvent::OwnerChanged(1, 1, 5)),
+            TestEvent::nft(RawEvent::CollectionDestroyed(1)),
+        ]);
+    });
+}
+
+
+#[test]
+fn collection_admins_are_limited() {
+    new_test_ext().execute_with(|| {
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+
+        for admin in 0..MAX_COLLECTION_ADMINS {
+            assert_ok!(TemplateModule::add_collection_admin(Origin::signed(1), 1, 100 + admin as u64));
+        }
+        assert_noop!(TemplateModule::add_collection_admin(Origin::signed(1), 1, 99), Error::<Test>::CollectionAdminsLimitExceeded);
+    });
+}
+
+#[test]
+fn refungible_owners_are_limited() {
+    new_test_ext().execute_with(|| {
+        create_test_collection(&CollectionMode::ReFungible(2000, 3), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 1));
+
+        for owner in 1..MAX_REFUNGIBLE_OWNERS {
+            assert_ok!(TemplateModule::transfer(Origin::signed(1), 100 + owner as u64, 1, 1, 1));
+        }
+        assert_noop!(TemplateModule::transfer(Origin::signed(1), 99, 1, 1, 1), Error::<Test>::RefungibleOwnersLimitExceeded);
+
+        // existing owners can still receive parts
+        assert_ok!(TemplateModule::transfer(Origin::signed(1), 101, 1, 1, 1));
+    });
+}
+
+
+#[test]
+fn destroy_collection_removes_items() {
+    new_test_ext().execute_with(|| {
+        create_test_collection(&CollectionMode::ReFungible(2000, 3), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 1));
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 1));
+        assert_ok!(TemplateModule::transfer(Origin::signed(1), 2, 1, 1, 400));
+        assert_ok!(TemplateModule::approve(Origin::signed(1), 3, 1, 2, 1));
+        assert_ok!(TemplateModule::add_to_white_list(Origin::signed(1), 1, 2));
+
+        assert_ok!(TemplateModule::destroy_collection(Origin::signed(1), 1));
+
+        assert!(!<Collection<Test>>::contains_key(1));
+        assert!(!<ReFungibleItemList<Test>>::contains_key(1, 1));
+        assert!(!<ReFungibleItemList<Test>>::contains_key(1, 2));
+        assert!(!<ApprovedList<Test>>::contains_key(1, 2));
+        assert!(!<DestroyingCollection>::contains_key(1));
+        assert_eq!(TemplateModule::balance_count(1,1), 0);
+        assert_eq!(TemplateModule::balance_count(1,2), 0);
+        assert_eq!(TemplateModule::address_tokens(1,1), []);
+        assert_eq!(TemplateModule::address_tokens(1,2), []);
+        assert!(!TemplateModule::white_list(1, 2));
+        assert_eq!(TemplateModule::destroy_queue(0), None);
+
+        assert_noop!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 1), Error::<Test>::CollectionNotFound);
+        assert_noop!(TemplateModule::transfer(Origin::signed(2), 3, 1, 1, 100), Error::<Test>::CollectionNotFound);
+    });
+}
+
+#[test]
+fn destroy_large_collection_across_blocks() {
+    new_test_ext().execute_with(|| {
+        let items = COLLECTION_CLEANUP_ITEMS_LIMIT as u64 + 5;
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        for _ in 0..items {
+            assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 1));
+        }
+
+        assert_ok!(TemplateModule::destroy_collection(Origin::signed(1), 1));
+
+        // first chunk is removed by the call itself
+        assert!(!<NftItemList<Test>>::contains_key(1, COLLECTION_CLEANUP_ITEMS_LIMIT as u64));
+        assert!(<NftItemList<Test>>::contains_key(1, COLLECTION_CLEANUP_ITEMS_LIMIT as u64 + 1));
+        assert_eq!(TemplateModule::destroy_queue(0), Some(1));
+        assert_eq!(TemplateModule::destroying_collection(1).next_item_id, COLLECTION_CLEANUP_ITEMS_LIMIT as u64 + 1);
+
+        // the rest is removed in the next block
+        TemplateModule::on_initialize(2);
+        assert!(!<NftItemList<Test>>::contains_key(1, items));
+        assert!(!<DestroyingCollection>::contains_key(1));
+        assert_eq!(TemplateModule::destroy_queue(0), None);
+        assert_eq!(TemplateModule::balance_count(1,1), 0);
+        assert_eq!(TemplateModule::address_tokens(1,1), []);
+    });
+}
+
+#[test]
+fn destroy_collection_removes_approvals_across_blocks() {
+    new_test_ext().execute_with(|| {
+        // 5 owners approve MAX_APPROVED_PER_OWNER spenders each
+        create_test_collection(&CollectionMode::ReFungible(2000, 3), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 1));
+        for owner in 1..=5u64 {
+            if owner > 1 {
+                assert_ok!(TemplateModule::transfer(Origin::signed(1), owner, 1, 1, 100));
+            }
+            for i in 0..MAX_APPROVED_PER_OWNER as u64 {
+                assert_ok!(TemplateModule::approve(Origin::signed(owner), 100 + owner * 100 + i, 1, 1, 1));
+            }
+        }
+
+        // approval entries count against the cleanup limit
+        assert_ok!(TemplateModule::destroy_collection(Origin::signed(1), 1));
+        assert!(<ReFungibleItemList<Test>>::contains_key(1, 1));
+        assert_eq!(<Allowances<Test>>::iter_prefix((1, 1)).count(), 80 - COLLECTION_CLEANUP_ITEMS_LIMIT as usize);
+
+        TemplateModule::on_initialize(2);
+        assert!(<ReFungibleItemList<Test>>::contains_key(1, 1));
+        assert_eq!(<Allowances<Test>>::iter_prefix((1, 1)).count(), 0);
+
+        TemplateModule::on_initialize(3);
+        assert!(!<ReFungibleItemList<Test>>::contains_key(1, 1));
+        assert!(!<ApprovedList<Test>>::contains_key(1, 1));
+        assert!(!<DestroyingCollection>::contains_key(1));
+        assert!(!ItemListIndex::contains_key(1));
+        assert_eq!(<Balance<Test>>::iter_prefix(1).count(), 0);
+        assert_eq!(TemplateModule::destroy_queue(0), None);
+    });
+}
+
+#[test]
+fn destroy_collection_removes_account_entries_across_blocks() {
+    new_test_ext().execute_with(|| {
+        // every previous owner keeps a zero balance and an empty token list
+        let owners = COLLECTION_CLEANUP_ITEMS_LIMIT as u64;
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 1));
+        for owner in 1..=owners {
+            assert_ok!(TemplateModule::transfer(Origin::signed(owner), owner + 1, 1, 1, 1));
+        }
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+
+        assert_ok!(TemplateModule::destroy_collection(Origin::signed(1), 1));
+        assert!(!<NftItemList<Test>>::contains_key(1, 1));
+        assert!(<DestroyingCollection>::contains_key(1));
+
+        TemplateModule::on_initialize(2);
+        assert!(<DestroyingCollection>::contains_key(1));
+        assert_eq!(<Balance<Test>>::iter_prefix(1).count(), 1);
+
+        // the last entry of the first collection and the whole second one
+        assert_ok!(TemplateModule::destroy_collection(Origin::signed(1), 2));
+        assert!(!<DestroyingCollection>::contains_key(1));
+        assert!(!<DestroyingCollection>::contains_key(2));
+        assert_eq!(<Balance<Test>>::iter_prefix(1).count(), 0);
+        assert_eq!(<AddressTokens<Test>>::iter_prefix(1).count(), 0);
+        assert_eq!(TemplateModule::destroy_queue(0), None);
+        assert_eq!(TemplateModule::destroy_queue(1), None);
+    });
+}
+
+#[test]
+fn white_list_restricts_recipients() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 1));
+
+        assert_noop!(TemplateModule::set_public_access_mode(Origin::signed(2), 1, AccessMode::WhiteList), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::add_to_white_list(Origin::signed(2), 1, 2), Error::<Test>::NoPermission);
+
+        assert_ok!(TemplateModule::set_public_access_mode(Origin::signed(1), 1, AccessMode::WhiteList));
+        assert_eq!(TemplateModule::collection(1).access, AccessMode::WhiteList);
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::AccessModeSet(1, AccessMode::WhiteList))));
+
+        // recipients must be white listed
+        assert_noop!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 2), Error::<Test>::AddressNotInWhiteList);
+        assert_noop!(TemplateModule::transfer(Origin::signed(1), 2, 1, 1, 1), Error::<Test>::AddressNotInWhiteList);
+
+        assert_ok!(TemplateModule::add_to_white_list(Origin::signed(1), 1, 2));
+        assert!(TemplateModule::white_list(1, 2));
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::WhiteListAdded(1, 2))));
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 2));
+        assert_ok!(TemplateModule::transfer(Origin::signed(1), 2, 1, 1, 1));
+        assert_eq!(TemplateModule::nft_item_id(1, 1).owner, 2);
+
+        assert_ok!(TemplateModule::remove_from_white_list(Origin::signed(1), 1, 2));
+        assert!(!TemplateModule::white_list(1, 2));
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::WhiteListRemoved(1, 2))));
+        assert_noop!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 2), Error::<Test>::AddressNotInWhiteList);
+
+        // normal mode does not check the white list
+        assert_ok!(TemplateModule::set_public_access_mode(Origin::signed(1), 1, AccessMode::Normal));
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 3));
+    });
+}
+
+#[test]
+fn public_mint_permission() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+
+        // only owner and admins can mint by default
+        assert_noop!(TemplateModule::create_item(Origin::signed(2), 1, [1,2,3].to_vec(), 2), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::set_mint_permission(Origin::signed(2), 1, true, 0), Error::<Test>::NoPermission);
+
+        assert_ok!(TemplateModule::set_mint_permission(Origin::signed(1), 1, true, 2));
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::MintPermissionSet(1, true, 2))));
+
+        // anyone can mint for themselves, up to the limit
+        assert_noop!(TemplateModule::create_item(Origin::signed(2), 1, [1,2,3].to_vec(), 3), Error::<Test>::NoPermission);
+        assert_ok!(TemplateModule::create_item(Origin::signed(2), 1, [1,2,3].to_vec(), 2));
+        assert_ok!(TemplateModule::create_item(Origin::signed(2), 1, [1,2,3].to_vec(), 2));
+        assert_eq!(TemplateModule::account_minted(1, 2), 2);
+        assert_noop!(TemplateModule::create_item(Origin::signed(2), 1, [1,2,3].to_vec(), 2), Error::<Test>::AccountMintLimitExceeded);
+
+        // the limit does not apply to the owner
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 1));
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 1));
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 1));
+        assert_eq!(TemplateModule::account_minted(1, 1), 0);
+
+        // in white list mode only white listed accounts can mint
+        assert_ok!(TemplateModule::set_public_access_mode(Origin::signed(1), 1, AccessMode::WhiteList));
+        assert_noop!(TemplateModule::create_item(Origin::signed(3), 1, [1,2,3].to_vec(), 3), Error::<Test>::AddressNotInWhiteList);
+        assert_ok!(TemplateModule::add_to_white_list(Origin::signed(1), 1, 3));
+        assert_ok!(TemplateModule::create_item(Origin::signed(3), 1, [1,2,3].to_vec(), 3));
+
+        assert_ok!(TemplateModule::set_mint_permission(Origin::signed(1), 1, false, 0));
+        assert_noop!(TemplateModule::create_item(Origin::signed(3), 1, [1,2,3].to_vec(), 3), Error::<Test>::NoPermission);
+
+        // minted counters are removed with the collection
+        assert_ok!(TemplateModule::destroy_collection(Origin::signed(1), 1));
+        assert_eq!(TemplateModule::account_minted(1, 2), 0);
+        assert_eq!(TemplateModule::account_minted(1, 3), 0);
+    });
+}
+
+#[test]
+fn contract_sponsorship() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let contract = 100;
+        <ContractOwner<Test>>::insert(contract, 1);
+
+        assert_noop!(TemplateModule::set_contract_sponsor(Origin::signed(2), contract, 2), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::set_contract_sponsor(Origin::signed(1), 101, 2), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::confirm_contract_sponsorship(Origin::signed(2), contract), Error::<Test>::ConfirmUnsetSponsorFail);
+
+        assert_ok!(TemplateModule::set_contract_sponsor(Origin::signed(1), contract, 2));
+        assert_eq!(TemplateModule::unconfirmed_contract_sponsor(contract), 2);
+        assert_noop!(TemplateModule::confirm_contract_sponsorship(Origin::signed(3), contract), Error::<Test>::ConfirmUnsetSponsorFail);
+
+        assert_ok!(TemplateModule::confirm_contract_sponsorship(Origin::signed(2), contract));
+        assert_eq!(TemplateModule::contract_sponsor(contract), 2);
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::ContractSponsorConfirmed(contract, 2))));
+
+        assert_noop!(TemplateModule::remove_contract_sponsor(Origin::signed(2), contract), Error::<Test>::NoPermission);
+        assert_ok!(TemplateModule::remove_contract_sponsor(Origin::signed(1), contract));
+        assert_eq!(TemplateModule::contract_sponsor(contract), 0);
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::ContractSponsorRemoved(contract))));
+    });
+}
+
+#[test]
+fn contract_owner_is_recorded_after_instantiation() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let wasm = wat::parse_file("fixtures/accept_nft.wat").unwrap();
+        let code_hash = <Test as frame_system::Trait>::Hashing::hash(&wasm);
+        assert_ok!(Contracts::put_code(Origin::signed(1), wasm));
+        let contract = <Test as contracts::Trait>::DetermineContractAddress::contract_address_for(&code_hash, &[], &2);
+
+        let info = DispatchInfo { weight: 100, class: DispatchClass::Normal, pays_fee: Pays::Yes };
+        let post_info = PostDispatchInfo { actual_weight: None };
+        let len = 10;
+        let call = Call::Contracts(contracts::Call::instantiate(100_000, 10_000_000_000, code_hash, Vec::new()));
+
+        // failed instantiation gets no owner
+        let pre = ChargeTransactionPayment::<Test>::from(0).pre_dispatch(&2, &call, &info, len).unwrap();
+        assert_ok!(ChargeTransactionPayment::<Test>::post_dispatch(pre, &info, &post_info, len, &Err(DispatchError::Other("failed"))));
+        assert!(!<ContractOwner<Test>>::contains_key(contract));
+
+        let pre = ChargeTransactionPayment::<Test>::from(0).pre_dispatch(&2, &call, &info, len).unwrap();
+        assert_ok!(Contracts::instantiate(Origin::signed(2), 100_000, 10_000_000_000, code_hash, Vec::new()));
+        assert_ok!(ChargeTransactionPayment::<Test>::post_dispatch(pre, &info, &post_info, len, &Ok(())));
+        assert_eq!(TemplateModule::contract_owner(contract), 2);
+
+        // owner is not replaced
+        let pre = ChargeTransactionPayment::<Test>::from(0).pre_dispatch(&2, &call, &info, len).unwrap();
+        <ContractOwner<Test>>::insert(contract, 3);
+        assert_ok!(ChargeTransactionPayment::<Test>::post_dispatch(pre, &info, &post_info, len, &Ok(())));
+        assert_eq!(TemplateModule::contract_owner(contract), 3);
+
+        // contracts instantiated by other contracts or before owners were recorded
+        assert!(!<ContractOwner<Test>>::contains_key(contract + 1));
+        assert_noop!(TemplateModule::set_contract_owner(Origin::signed(1), contract + 1, 1), DispatchError::BadOrigin);
+        assert_ok!(TemplateModule::set_contract_owner(RawOrigin::Root.into(), contract + 1, 1));
+        assert_eq!(TemplateModule::contract_owner(contract + 1), 1);
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::ContractOwnerSet(contract + 1, 1))));
+    });
+}
+
+#[test]
+fn contract_sponsorship_limits() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let contract = 100;
+        <ContractOwner<Test>>::insert(contract, 2);
+        assert_ok!(TemplateModule::set_contract_sponsor(Origin::signed(2), contract, 6));
+        assert_ok!(TemplateModule::confirm_contract_sponsorship(Origin::signed(6), contract));
+
+        let limits = ContractSponsorshipLimitsType {
+            call_timeout: 5,
+            block_fee_limit: 200,
+            max_tx_fee: TEST_CALL_FEE as u128,
+        };
+        assert_noop!(TemplateModule::set_contract_sponsorship_limits(Origin::signed(6), contract, limits.clone()), Error::<Test>::NoPermission);
+        assert_ok!(TemplateModule::set_contract_sponsorship_limits(Origin::signed(2), contract, limits.clone()));
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::ContractSponsorshipLimitsSet(contract, limits.clone()))));
+
+        let call = Call::Contracts(contracts::Call::call(contract, 0, 0, Vec::new()));
+        charge_test_call(3, &call);
+        assert_eq!(Balances::free_balance(3), 1_000_000);
+        assert_eq!(Balances::free_balance(6), 1_000_000 - TEST_CALL_ACTUAL_FEE);
+
+        // account timeout
+        charge_test_call(3, &call);
+        assert_eq!(Balances::free_balance(3), 1_000_000 - TEST_CALL_ACTUAL_FEE);
+        // block budget
+        charge_test_call(4, &call);
+        assert_eq!(Balances::free_balance(4), 1_000_000 - TEST_CALL_ACTUAL_FEE);
+        assert_eq!(Balances::free_balance(6), 1_000_000 - TEST_CALL_ACTUAL_FEE);
+
+        // budget is renewed every block, timeouts expire
+        System::set_block_number(6);
+        charge_test_call(3, &call);
+        assert_eq!(Balances::free_balance(3), 1_000_000 - TEST_CALL_ACTUAL_FEE);
+        assert_eq!(Balances::free_balance(6), 1_000_000 - 2 * TEST_CALL_ACTUAL_FEE);
+
+        // fee is too high
+        assert_ok!(TemplateModule::set_contract_sponsorship_limits(Origin::signed(2), contract, ContractSponsorshipLimitsType {
+            max_tx_fee: TEST_CALL_FEE as u128 - 1,
+            ..limits
+        }));
+        charge_test_call(5, &call);
+        assert_eq!(Balances::free_balance(5), 1_000_000 - TEST_CALL_ACTUAL_FEE);
+    });
+}
+// Sponsor of a collection call, the call counts towards sponsorship limits
+// if it is sponsored
+fn use_collection_sponsorship(collection_id: u64, who: &u64, item: Option<(u64, u32)>, fee: u128) -> u64 {
+    let sponsor = TemplateModule::check_collection_sponsorship(collection_id, who, item, fee);
+    if sponsor != 0 {
+        TemplateModule::record_collection_sponsorship(collection_id, who, item, 1, fee);
+    }
+    sponsor
+}
+
+#[test]
+fn sponsorship_limits() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+
+        // not sponsored collection
+        assert_eq!(use_collection_sponsorship(1, &2, None, 100), 0);
+
+        assert_ok!(TemplateModule::set_collection_sponsor(Origin::signed(1), 1, 5));
+        assert_ok!(TemplateModule::confirm_sponsorship(Origin::signed(5), 1));
+        assert_eq!(use_collection_sponsorship(1, &2, None, 100), 5);
+
+        let limits = SponsorshipLimitsType {
+            create_item_timeout: 10,
+            transfer_timeout: 5,
+            block_fee_limit: 250,
+            max_tx_fee: 100,
+        };
+        assert_noop!(TemplateModule::set_sponsorship_limits(Origin::signed(2), 1, limits.clone()), Error::<Test>::NoPermission);
+        assert_ok!(TemplateModule::set_sponsorship_limits(Origin::signed(1), 1, limits.clone()));
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::SponsorshipLimitsSet(1, limits.clone()))));
+
+        System::set_block_number(2);
+        // fee is too high
+        assert_eq!(use_collection_sponsorship(1, &2, None, 101), 0);
+        assert_eq!(use_collection_sponsorship(1, &2, None, 100), 5);
+        // account timeout
+        assert_eq!(use_collection_sponsorship(1, &2, None, 100), 0);
+        // item timeout
+        assert_eq!(use_collection_sponsorship(1, &2, Some((1, SPONSOR_TRANSFER)), 100), 5);
+        assert_eq!(use_collection_sponsorship(1, &3, Some((1, SPONSOR_TRANSFER)), 100), 0);
+        // block budget
+        assert_eq!(use_collection_sponsorship(1, &3, None, 100), 0);
+        assert_eq!(use_collection_sponsorship(1, &3, None, 50), 5);
+
+        // budget is renewed every block, timeouts expire
+        System::set_block_number(7);
+        assert_eq!(use_collection_sponsorship(1, &3, Some((1, SPONSOR_TRANSFER)), 100), 5);
+        // other calls of the item have their own timeout
+        assert_eq!(use_collection_sponsorship(1, &3, Some((1, SPONSOR_APPROVE)), 100), 5);
+        assert_eq!(use_collection_sponsorship(1, &2, None, 100), 0);
+        System::set_block_number(12);
+        assert_eq!(use_collection_sponsorship(1, &2, None, 100), 5);
+
+        // sponsorship state is removed with the collection
+        assert_ok!(TemplateModule::destroy_collection(Origin::signed(1), 1));
+        assert_eq!(TemplateModule::create_item_sponsor_basket(1, 2), None);
+        assert_eq!(TemplateModule::transfer_sponsor_basket(1, (1, SPONSOR_TRANSFER)), None);
+        assert_eq!(TemplateModule::sponsored_fees(1), (0, 0));
+    });
+}
+
+#[test]
+fn select_fee_model() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+
+        // user paid by default
+        assert_eq!(TemplateModule::collection(1).fee_model, FeeModel::UserPaid);
+        assert_eq!(TemplateModule::collection_fee_payer(1, &2, None, 100), 0);
+
+        // confirmed sponsor pays by default
+        assert_ok!(TemplateModule::set_collection_sponsor(Origin::signed(1), 1, 5));
+        assert_ok!(TemplateModule::confirm_sponsorship(Origin::signed(5), 1));
+        assert_eq!(TemplateModule::collection(1).fee_model, FeeModel::PayAsYouGo);
+        assert_eq!(TemplateModule::collection_fee_payer(1, &2, None, 100), 5);
+
+        assert_noop!(TemplateModule::select_fee_model(Origin::signed(2), 1, FeeModel::UserPaid), Error::<Test>::NoPermission);
+
+        assert_ok!(TemplateModule::select_fee_model(Origin::signed(1), 1, FeeModel::UserPaid));
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::FeeModelSet(1, FeeModel::UserPaid))));
+        assert_eq!(TemplateModule::collection_fee_payer(1, &2, None, 100), 0);
+
+        // selected fee model is kept when sponsor is confirmed
+        assert_ok!(TemplateModule::set_collection_sponsor(Origin::signed(1), 1, 6));
+        assert_ok!(TemplateModule::select_fee_model(Origin::signed(1), 1, FeeModel::PrepaidPlan));
+        assert_ok!(TemplateModule::confirm_sponsorship(Origin::signed(6), 1));
+        assert_eq!(TemplateModule::collection(1).fee_model, FeeModel::PrepaidPlan);
+    });
+}
+
+#[test]
+fn prepaid_collection_deposit() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        let deposit_account = TemplateModule::collection_deposit_account(1);
+        assert_ne!(deposit_account, TemplateModule::collection_deposit_account(2));
+
+        assert_noop!(TemplateModule::deposit_to_collection(Origin::signed(1), 2, 1000), Error::<Test>::CollectionNotFound);
+        assert_ok!(TemplateModule::deposit_to_collection(Origin::signed(1), 1, 1000));
+        assert_ok!(TemplateModule::deposit_to_collection(Origin::signed(2), 1, 500));
+        assert_eq!(Balances::free_balance(deposit_account), 1500);
+        assert_eq!(Balances::free_balance(1), 999_000);
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::CollectionDeposited(1, 2, 500))));
+
+        assert_noop!(TemplateModule::withdraw_from_collection(Origin::signed(2), 1, 100), Error::<Test>::NoPermission);
+        assert_ok!(TemplateModule::withdraw_from_collection(Origin::signed(1), 1, 100));
+        assert_eq!(Balances::free_balance(deposit_account), 1400);
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::CollectionWithdrawn(1, 1, 100))));
+
+        // fees are paid from the deposit in prepaid plan
+        assert_eq!(TemplateModule::collection_fee_payer(1, &2, None, 100), 0);
+        assert_ok!(TemplateModule::select_fee_model(Origin::signed(1), 1, FeeModel::PrepaidPlan));
+        assert_eq!(TemplateModule::collection_fee_payer(1, &2, None, 100), deposit_account);
+
+        // the rest of the deposit is returned to the owner
+        assert_ok!(TemplateModule::destroy_collection(Origin::signed(1), 1));
+        assert_eq!(Balances::free_balance(deposit_account), 0);
+        assert_eq!(Balances::free_balance(1), 999_100 + 1400);
+    });
+}
+
+#[test]
+fn buy_resources() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+
+        assert_noop!(TemplateModule::buy_resources(Origin::signed(2), 1, 10), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::buy_resources(Origin::signed(1), 1, u64::max_value()), Error::<Test>::NumOverflow);
+
+        assert_ok!(TemplateModule::buy_resources(Origin::signed(1), 1, 2));
+        assert_eq!(TemplateModule::resource_quota(1), 2);
+        assert_eq!(Balances::free_balance(1), 1_000_000 - 2 * ResourceTxPrice::get());
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::ResourcesBought(1, 1, 2, 2 * ResourceTxPrice::get()))));
+
+        // quota is used in resource purchase model only
+        assert!(!TemplateModule::use_resource_quota(1, 1));
+        assert_ok!(TemplateModule::select_fee_model(Origin::signed(1), 1, FeeModel::ResourcePurchase));
+        assert!(TemplateModule::use_resource_quota(1, 1));
+        assert!(!TemplateModule::use_resource_quota(1, 2));
+        assert!(TemplateModule::use_resource_quota(1, 1));
+        assert!(!TemplateModule::use_resource_quota(1, 1));
+        assert_eq!(TemplateModule::resource_quota(1), 0);
+    });
+}
+
+#[test]
+fn fees_are_split_between_treasury_and_author() {
+    new_test_ext().execute_with(|| {
+        let fee = Balances::withdraw(&1, 1000, WithdrawReason::TransactionPayment.into(), ExistenceRequirement::KeepAlive).unwrap();
+        FeeSplit::<Test, TreasuryAccount, TreasuryShare, FixedAuthor>::on_unbalanced(fee);
+        assert_eq!(Balances::free_balance(20), 200);
+        assert_eq!(Balances::free_balance(21), 800);
+
+        let fee = Balances::withdraw(&1, 1000, WithdrawReason::TransactionPayment.into(), ExistenceRequirement::KeepAlive).unwrap();
+        FeeSplit::<Test, TreasuryAccount, TreasuryShare, UnknownAuthor>::on_unbalanced(fee);
+        assert_eq!(Balances::free_balance(20), 1200);
+        assert_eq!(Balances::free_balance(21), 800);
+        assert_eq!(Balances::total_issuance(), 9 * 1_000_000);
+    });
+}
+
+#[test]
+fn governance_sets_fees() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_eq!(TemplateModule::create_collection_fee(), CollectionCreationFee::get());
+
+        assert_noop!(TemplateModule::set_create_collection_fee(Origin::signed(1), Some(5)), DispatchError::BadOrigin);
+        assert_ok!(TemplateModule::set_create_collection_fee(RawOrigin::Root.into(), Some(5)));
+        assert_eq!(TemplateModule::create_collection_fee(), 5);
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::CreateCollectionFeeSet(Some(5)))));
+        assert_ok!(TemplateModule::set_create_collection_fee(RawOrigin::Root.into(), None));
+        assert_eq!(TemplateModule::create_collection_fee(), CollectionCreationFee::get());
+
+        assert_noop!(TemplateModule::set_call_flat_fee(Origin::signed(1), b"transfer".to_vec(), Some(7)), DispatchError::BadOrigin);
+        assert_noop!(TemplateModule::set_call_flat_fee(RawOrigin::Root.into(), b"no_such_call".to_vec(), Some(7)), Error::<Test>::UnknownCall);
+        assert_ok!(TemplateModule::set_call_flat_fee(RawOrigin::Root.into(), b"transfer".to_vec(), Some(7)));
+        assert_eq!(TemplateModule::call_flat_fee(b"transfer".to_vec()), Some(7));
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::CallFlatFeeSet(b"transfer".to_vec(), Some(7)))));
+        assert_ok!(TemplateModule::set_call_flat_fee(RawOrigin::Root.into(), b"transfer".to_vec(), None));
+        assert_eq!(TemplateModule::call_flat_fee(b"transfer".to_vec()), None);
+    });
+}
+// Fee of the test calls: base weight 10, weight 100 and length 10
+const TEST_CALL_FEE: u64 = 120;
+// Actual fee of the test calls after dispatch with actual weight 50
+const TEST_CALL_ACTUAL_FEE: u64 = 70;
+
+// Charge fee of the call signed by `who`, dispatch it using half of its weight
+// and process the refund
+fn charge_test_call(who: u64, call: &Call) {
+    let info = DispatchInfo { weight: 100, class: DispatchClass::Normal, pays_fee: Pays::Yes };
+    let post_info = PostDispatchInfo { actual_weight: Some(50) };
+    let len = 10;
+
+    let pre = ChargeTransactionPayment::<Test>::from(0).pre_dispatch(&who, call, &info, len).unwrap();
+    assert_ok!(ChargeTransactionPayment::<Test>::post_dispatch(pre, &info, &post_info, len, &Ok(())));
+}
+
+#[test]
+fn flat_and_creation_fees_are_charged() {
+    new_test_ext().execute_with(|| {
+        let create_collection = Call::TemplateModule(crate::Call::create_collection(
+            "Test1".encode_utf16().collect::<Vec<u16>>(),
+            "TestDescription1".encode_utf16().collect::<Vec<u16>>(),
+            b"token_prefix1".to_vec(),
+            CollectionMode::NFT(2000),
+        ));
+
+        // creation fee is charged in addition to weight fee
+        charge_test_call(1, &create_collection);
+        assert_eq!(Balances::free_balance(1), 1_000_000 - CollectionCreationFee::get() - TEST_CALL_ACTUAL_FEE);
+
+        // and in addition to flat fee, which is not refunded
+        assert_ok!(TemplateModule::set_call_flat_fee(RawOrigin::Root.into(), b"create_collection".to_vec(), Some(7)));
+        charge_test_call(2, &create_collection);
+        assert_eq!(Balances::free_balance(2), 1_000_000 - CollectionCreationFee::get() - 7);
+
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 3));
+        let transfer = Call::TemplateModule(crate::Call::transfer(4, 1, 1, 1));
+        charge_test_call(3, &transfer);
+        assert_eq!(Balances::free_balance(3), 1_000_000 - TEST_CALL_ACTUAL_FEE);
+
+        assert_ok!(TemplateModule::set_call_flat_fee(RawOrigin::Root.into(), b"transfer".to_vec(), Some(7)));
+        charge_test_call(3, &transfer);
+        assert_eq!(Balances::free_balance(3), 1_000_000 - TEST_CALL_ACTUAL_FEE - 7);
+    });
+}
+
+#[test]
+fn refund_goes_to_fee_payer() {
+    new_test_ext().execute_with(|| {
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        let call = Call::TemplateModule(crate::Call::create_item(1, [1,2,3].to_vec(), 1));
+
+        // user paid
+        charge_test_call(1, &call);
+        assert_eq!(Balances::free_balance(1), 1_000_000 - TEST_CALL_ACTUAL_FEE);
+
+        // sponsored
+        assert_ok!(TemplateModule::set_collection_sponsor(Origin::signed(1), 1, 5));
+        assert_ok!(TemplateModule::confirm_sponsorship(Origin::signed(5), 1));
+        charge_test_call(1, &call);
+        assert_eq!(Balances::free_balance(1), 1_000_000 - TEST_CALL_ACTUAL_FEE);
+        assert_eq!(Balances::free_balance(5), 1_000_000 - TEST_CALL_ACTUAL_FEE);
+
+        // sponsored contract call
+        let contract = 100;
+        <ContractOwner<Test>>::insert(contract, 2);
+        assert_ok!(TemplateModule::set_contract_sponsor(Origin::signed(2), contract, 6));
+        assert_ok!(TemplateModule::confirm_contract_sponsorship(Origin::signed(6), contract));
+        charge_test_call(3, &Call::Contracts(contracts::Call::call(contract, 0, 0, Vec::new())));
+        assert_eq!(Balances::free_balance(3), 1_000_000);
+        assert_eq!(Balances::free_balance(6), 1_000_000 - TEST_CALL_ACTUAL_FEE);
+    });
+}
+
+#[test]
+fn failed_sponsor_payment_is_not_counted() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        // sponsor without funds
+        assert_ok!(TemplateModule::set_collection_sponsor(Origin::signed(1), 1, 15));
+        assert_ok!(TemplateModule::confirm_sponsorship(Origin::signed(15), 1));
+        assert_ok!(TemplateModule::select_fee_model(Origin::signed(1), 1, FeeModel::PayAsYouGo));
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 2));
+
+        charge_test_call(1, &Call::TemplateModule(crate::Call::create_item(1, [1,2,3].to_vec(), 1)));
+        charge_test_call(2, &Call::TemplateModule(crate::Call::transfer(3, 1, 1, 1)));
+        assert_eq!(Balances::free_balance(1), 1_000_000 - TEST_CALL_ACTUAL_FEE);
+        assert_eq!(Balances::free_balance(2), 1_000_000 - TEST_CALL_ACTUAL_FEE);
+        assert_eq!(TemplateModule::create_item_sponsor_basket(1, 1), None);
+        assert_eq!(TemplateModule::transfer_sponsor_basket(1, (1, SPONSOR_TRANSFER)), None);
+        assert_eq!(TemplateModule::sponsored_fees(1), (0, 0));
+    });
+}
+
+#[test]
+fn prepaid_deposit_pays_fees_until_exhausted() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        let deposit_account = TemplateModule::collection_deposit_account(1);
+        assert_ok!(TemplateModule::select_fee_model(Origin::signed(1), 1, FeeModel::PrepaidPlan));
+        assert_ok!(TemplateModule::deposit_to_collection(Origin::signed(1), 1, TEST_CALL_FEE + 1));
+        assert_ok!(TemplateModule::set_mint_permission(Origin::signed(1), 1, true, 0));
+        let call = Call::TemplateModule(crate::Call::create_item(1, [1,2,3].to_vec(), 2));
+
+        // account 3 can not create items for account 2, it pays itself
+        charge_test_call(3, &call);
+        assert_eq!(Balances::free_balance(3), 1_000_000 - TEST_CALL_ACTUAL_FEE);
+        assert_eq!(Balances::free_balance(deposit_account), TEST_CALL_FEE + 1);
+
+        // the call exceeds sponsorship limits, sender pays
+        let limits = SponsorshipLimitsType { max_tx_fee: TEST_CALL_FEE - 1, ..Default::default() };
+        assert_ok!(TemplateModule::set_sponsorship_limits(Origin::signed(1), 1, limits));
+        charge_test_call(2, &call);
+        assert_eq!(Balances::free_balance(2), 1_000_000 - TEST_CALL_ACTUAL_FEE);
+        assert_eq!(Balances::free_balance(deposit_account), TEST_CALL_FEE + 1);
+
+        assert_ok!(TemplateModule::set_sponsorship_limits(Origin::signed(1), 1, Default::default()));
+        charge_test_call(2, &call);
+        assert_eq!(Balances::free_balance(2), 1_000_000 - TEST_CALL_ACTUAL_FEE);
+        assert_eq!(Balances::free_balance(deposit_account), TEST_CALL_FEE + 1 - TEST_CALL_ACTUAL_FEE);
+        assert_eq!(TemplateModule::create_item_sponsor_basket(1, 2), Some(1));
+
+        // deposit can not pay the full fee, sender pays
+        charge_test_call(2, &call);
+        assert_eq!(Balances::free_balance(2), 1_000_000 - 2 * TEST_CALL_ACTUAL_FEE);
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::CollectionDepositExhausted(1))));
+    });
+}
+
+#[test]
+fn sponsored_calls_mask() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 2));
+        assert_ok!(TemplateModule::set_collection_sponsor(Origin::signed(1), 1, 5));
+        assert_ok!(TemplateModule::confirm_sponsorship(Origin::signed(5), 1));
+        assert_ok!(TemplateModule::select_fee_model(Origin::signed(1), 1, FeeModel::PayAsYouGo));
+        assert_eq!(TemplateModule::collection(1).unsponsored_calls, 0);
+
+        // all item calls are sponsored by default
+        charge_test_call(2, &Call::TemplateModule(crate::Call::approve(3, 1, 1, 1)));
+        charge_test_call(2, &Call::TemplateModule(crate::Call::transfer_from(2, 3, 1, 1, 1)));
+        charge_test_call(2, &Call::TemplateModule(crate::Call::burn_item(1, 1)));
+        assert_eq!(Balances::free_balance(2), 1_000_000);
+        assert_eq!(Balances::free_balance(5), 1_000_000 - 3 * TEST_CALL_ACTUAL_FEE);
+
+        assert_noop!(TemplateModule::set_sponsored_calls(Origin::signed(2), 1, SPONSOR_TRANSFER), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::set_sponsored_calls(Origin::signed(1), 1, SPONSOR_ALL_CALLS + 1), Error::<Test>::UnknownSponsoredCall);
+        assert_ok!(TemplateModule::set_sponsored_calls(Origin::signed(1), 1, SPONSOR_TRANSFER | SPONSOR_APPROVE));
+        assert_eq!(TemplateModule::collection(1).unsponsored_calls, SPONSOR_ALL_CALLS & !(SPONSOR_TRANSFER | SPONSOR_APPROVE));
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::SponsoredCallsSet(1, SPONSOR_TRANSFER | SPONSOR_APPROVE))));
+
+        // burn is not sponsored anymore
+        charge_test_call(2, &Call::TemplateModule(crate::Call::burn_item(1, 1)));
+        assert_eq!(Balances::free_balance(2), 1_000_000 - TEST_CALL_ACTUAL_FEE);
+        charge_test_call(2, &Call::TemplateModule(crate::Call::approve(3, 1, 1, 1)));
+        assert_eq!(Balances::free_balance(5), 1_000_000 - 4 * TEST_CALL_ACTUAL_FEE);
+    });
+}
+
+#[test]
+fn set_variable_data() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_test_collection(&CollectionMode::NFT(3), 1);
+        assert_ok!(TemplateModule::add_collection_admin(Origin::signed(1), 1, 2));
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 3));
+
+        // variable data is disabled by default
+        assert_noop!(TemplateModule::set_variable_data(Origin::signed(3), 1, 1, [4].to_vec()), Error::<Test>::DataTooLarge);
+
+        assert_noop!(TemplateModule::set_variable_data_policy(Origin::signed(3), 1, 4, VariableDataMode::ItemOwner), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::set_variable_data_policy(Origin::signed(1), 1, MAX_VARIABLE_DATA_SIZE + 1, VariableDataMode::ItemOwner), Error::<Test>::VariableDataSizeLimitExceeded);
+        assert_ok!(TemplateModule::set_variable_data_policy(Origin::signed(1), 1, 4, VariableDataMode::ItemOwner));
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::VariableDataPolicySet(1, 4, VariableDataMode::ItemOwner))));
+
+        assert_noop!(TemplateModule::set_variable_data(Origin::signed(2), 1, 1, [4].to_vec()), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::set_variable_data(Origin::signed(3), 1, 2, [4].to_vec()), Error::<Test>::ItemNotFound);
+        assert_noop!(TemplateModule::set_variable_data(Origin::signed(3), 1, 1, [4,5,6,7,8].to_vec()), Error::<Test>::DataTooLarge);
+        assert_ok!(TemplateModule::set_variable_data(Origin::signed(3), 1, 1, [4,5,6,7].to_vec()));
+        assert_eq!(TemplateModule::nft_item_id(1, 1).variable_data, [4,5,6,7].to_vec());
+        assert_eq!(TemplateModule::nft_item_id(1, 1).const_data, [1,2,3].to_vec());
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::VariableDataSet(1, 1))));
+
+        assert_ok!(TemplateModule::set_variable_data_policy(Origin::signed(1), 1, 4, VariableDataMode::Admin));
+        assert_noop!(TemplateModule::set_variable_data(Origin::signed(3), 1, 1, [5].to_vec()), Error::<Test>::NoPermission);
+        assert_ok!(TemplateModule::set_variable_data(Origin::signed(2), 1, 1, [5].to_vec()));
+
+        assert_ok!(TemplateModule::set_variable_data_policy(Origin::signed(1), 1, 4, VariableDataMode::ItemOwnerAndAdmin));
+        assert_ok!(TemplateModule::set_variable_data(Origin::signed(3), 1, 1, [6].to_vec()));
+        assert_ok!(TemplateModule::set_variable_data(Origin::signed(1), 1, 1, [7].to_vec()));
+        assert_eq!(TemplateModule::nft_item_id(1, 1).variable_data, [7].to_vec());
+
+        // fungible items have no data
+        create_test_collection(&CollectionMode::Fungible(3), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 2, [].to_vec(), 3));
+        assert_ok!(TemplateModule::set_variable_data_policy(Origin::signed(1), 2, 4, VariableDataMode::ItemOwner));
+        assert_noop!(TemplateModule::set_variable_data(Origin::signed(3), 2, 1, [1].to_vec()), Error::<Test>::UnexpectedCollectionType);
+    });
+}
+
+#[test]
+fn create_multiple_items() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_test_collection(&CollectionMode::NFT(3), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1].to_vec(), 2));
+
+        assert_noop!(TemplateModule::create_multiple_items(Origin::signed(1), 2, [([1].to_vec(), 2)].to_vec()), Error::<Test>::CollectionNotFound);
+        assert_noop!(TemplateModule::create_multiple_items(Origin::signed(1), 1, Vec::new()), Error::<Test>::EmptyBatch);
+        assert_noop!(TemplateModule::create_multiple_items(Origin::signed(1), 1, vec![([1].to_vec(), 2); MAX_ITEMS_PER_BATCH as usize + 1]), Error::<Test>::ItemsPerBatchLimitExceeded);
+        assert_noop!(TemplateModule::create_multiple_items(Origin::signed(2), 1, [([1].to_vec(), 2)].to_vec()), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::create_multiple_items(Origin::signed(1), 1, [([1].to_vec(), 2), ([1,2,3,4].to_vec(), 3)].to_vec()), Error::<Test>::DataTooLarge);
+
+        assert_ok!(TemplateModule::create_multiple_items(Origin::signed(1), 1, [([2].to_vec(), 3), ([3].to_vec(), 2), ([4].to_vec(), 3)].to_vec()));
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::ItemsCreated(1, 2, 4))));
+        assert_eq!(ItemListIndex::get(1), 4);
+        assert_eq!(TemplateModule::nft_item_id(1, 3).owner, 2);
+        assert_eq!(TemplateModule::nft_item_id(1, 4).const_data, [4].to_vec());
+        assert_eq!(TemplateModule::balance_count(1, 2), 2);
+        assert_eq!(TemplateModule::balance_count(1, 3), 2);
+        assert_eq!(TemplateModule::address_tokens(1, 2), [1, 3].to_vec());
+        assert_eq!(TemplateModule::address_tokens(1, 3), [2, 4].to_vec());
+
+        // public mint, for the sender only and up to the limit
+        assert_ok!(TemplateModule::set_mint_permission(Origin::signed(1), 1, true, 2));
+        assert_noop!(TemplateModule::create_multiple_items(Origin::signed(4), 1, [([1].to_vec(), 4), ([1].to_vec(), 5)].to_vec()), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::create_multiple_items(Origin::signed(4), 1, vec![([1].to_vec(), 4); 3]), Error::<Test>::AccountMintLimitExceeded);
+        assert_ok!(TemplateModule::create_multiple_items(Origin::signed(4), 1, vec![([1].to_vec(), 4); 2]));
+        assert_eq!(TemplateModule::account_minted(1, 4), 2);
+        assert_eq!(TemplateModule::balance_count(1, 4), 2);
+
+        // re-fungible items
+        create_test_collection(&CollectionMode::ReFungible(3, 2), 1);
+        assert_ok!(TemplateModule::create_multiple_items(Origin::signed(1), 2, [([1].to_vec(), 2), ([2].to_vec(), 2)].to_vec()));
+        assert_eq!(TemplateModule::refungible_item_id(2, 2).owner, [Ownership { owner: 2, fraction: 100 }].to_vec());
+        assert_eq!(TemplateModule::balance_count(2, 2), 200);
+    });
+}
+
+#[test]
+fn transfer_batch() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        create_test_collection(&CollectionMode::ReFungible(2000, 3), 1);
+        assert_ok!(TemplateModule::create_multiple_items(Origin::signed(1), 1, vec![([1].to_vec(), 1); 3]));
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 2, [1].to_vec(), 1));
+
+        assert_noop!(TemplateModule::transfer_batch(Origin::signed(1), Vec::new()), Error::<Test>::EmptyBatch);
+        assert_noop!(TemplateModule::transfer_batch(Origin::signed(1), [(2, 1, 1, 1), (3, 1, 1, 1)].to_vec()), Error::<Test>::BatchItemDuplicated);
+        // nothing moves if one of the transfers fails
+        assert_noop!(TemplateModule::transfer_batch(Origin::signed(1), [(2, 1, 1, 1), (2, 1, 4, 1)].to_vec()), Error::<Test>::ItemNotFound);
+        assert_noop!(TemplateModule::transfer_batch(Origin::signed(2), [(3, 1, 1, 1)].to_vec()), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::transfer_batch(Origin::signed(1), [(2, 1, 1, 1), (2, 2, 1, 1001)].to_vec()), Error::<Test>::InsufficientFraction);
+        // transfers written before a failed one are reverted
+        <Balance<Test>>::insert(1, 3, u64::max_value());
+        assert_noop!(TemplateModule::transfer_batch(Origin::signed(1), [(2, 1, 1, 1), (3, 1, 2, 1)].to_vec()), Error::<Test>::NumOverflow);
+        <Balance<Test>>::remove(1, 3);
+
+        assert_ok!(TemplateModule::transfer_batch(Origin::signed(1), [(2, 1, 1, 1), (3, 1, 3, 1), (2, 2, 1, 400)].to_vec()));
+        assert_eq!(TemplateModule::nft_item_id(1, 1).owner, 2);
+        assert_eq!(TemplateModule::nft_item_id(1, 2).owner, 1);
+        assert_eq!(TemplateModule::nft_item_id(1, 3).owner, 3);
+        assert_eq!(TemplateModule::balance_count(1, 1), 1);
+        assert_eq!(TemplateModule::balance_count(2, 2), 400);
+        assert_eq!(TemplateModule::balance_count(2, 1), 600);
+
+        let transfers = System::events().into_iter().filter(|r| match r.event {
+            TestEvent::nft(RawEvent::Transfer(..)) => true,
+            _ => false,
+        }).count();
+        assert_eq!(transfers, 3);
+
+        // white list of every collection is checked
+        assert_ok!(TemplateModule::set_public_access_mode(Origin::signed(1), 2, AccessMode::WhiteList));
+        assert_noop!(TemplateModule::transfer_batch(Origin::signed(1), [(4, 1, 2, 1), (4, 2, 1, 1)].to_vec()), Error::<Test>::AddressNotInWhiteList);
+    });
+}
+
+#[test]
+fn refungible_co_owner_can_not_take_other_parts() {
+    new_test_ext().execute_with(|| {
+        create_test_collection(&CollectionMode::ReFungible(2000, 3), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 2));
+        assert_ok!(TemplateModule::transfer(Origin::signed(2), 3, 1, 1, 400));
+
+        // owning a part of the item does not allow to move parts of others
+        assert_noop!(TemplateModule::transfer_from(Origin::signed(3), 2, 3, 1, 1, 100), Error::<Test>::NoPermission);
+        // neither does an approval of a co-owner
+        assert_ok!(TemplateModule::approve(Origin::signed(3), 4, 1, 1, 400));
+        assert_noop!(TemplateModule::transfer_from(Origin::signed(4), 2, 4, 1, 1, 100), Error::<Test>::NoPermission);
+        assert_ok!(TemplateModule::transfer_from(Origin::signed(4), 3, 4, 1, 1, 100));
+
+        // co-owner spends allowance of the other owner up to its amount
+        assert_ok!(TemplateModule::approve(Origin::signed(2), 3, 1, 1, 100));
+        assert_noop!(TemplateModule::transfer_from(Origin::signed(3), 2, 3, 1, 1, 200), Error::<Test>::AllowanceExceeded);
+        assert_ok!(TemplateModule::transfer_from(Origin::signed(3), 2, 3, 1, 1, 100));
+        assert_eq!(TemplateModule::allowance(1, 1, &2, &3), 0);
+
+        assert_eq!(TemplateModule::balance_count(1, 2), 500);
+        assert_eq!(TemplateModule::balance_count(1, 3), 400);
+        assert_eq!(TemplateModule::balance_count(1, 4), 100);
+    });
+}
+
+#[test]
+fn transfer_from_checks_caller() {
+    new_test_ext().execute_with(|| {
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        assert_ok!(TemplateModule::add_collection_admin(Origin::signed(1), 1, 5));
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 2));
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 2));
+        assert_ok!(TemplateModule::approve(Origin::signed(2), 3, 1, 1, 1));
+
+        // naming the approved account as recipient does not help others
+        assert_noop!(TemplateModule::transfer_from(Origin::signed(4), 2, 3, 1, 1, 1), Error::<Test>::NoPermission);
+        // admins and collection owner can not move items of users
+        assert_noop!(TemplateModule::transfer_from(Origin::signed(5), 2, 5, 1, 2, 1), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::transfer_from(Origin::signed(1), 2, 1, 1, 2, 1), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::approve(Origin::signed(5), 5, 1, 2, 1), Error::<Test>::NoPermission);
+        // approval covers one item of its owner
+        assert_noop!(TemplateModule::transfer_from(Origin::signed(3), 2, 3, 1, 2, 1), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::transfer_from(Origin::signed(3), 4, 3, 1, 1, 1), Error::<Test>::NoPermission);
+
+        assert_ok!(TemplateModule::transfer_from(Origin::signed(3), 2, 4, 1, 1, 1));
+        assert_eq!(TemplateModule::nft_item_id(1, 1).owner, 4);
+        assert!(TemplateModule::approved(1, 1).is_empty());
+        assert_noop!(TemplateModule::transfer_from(Origin::signed(3), 4, 3, 1, 1, 1), Error::<Test>::NoPermission);
+
+        // owner can use transfer_from for own items
+        assert_ok!(TemplateModule::transfer_from(Origin::signed(2), 2, 4, 1, 2, 1));
+        assert_eq!(TemplateModule::nft_item_id(1, 2).owner, 4);
+    });
+}
+
+#[test]
+fn transfer_from_decreases_allowance() {
+    new_test_ext().execute_with(|| {
+        create_test_collection(&CollectionMode::Fungible(3), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [].to_vec(), 2));
+        assert_ok!(TemplateModule::approve(Origin::signed(2), 3, 1, 1, 500));
+        assert_eq!(TemplateModule::allowance(1, 1, &2, &3), 500);
+
+        assert_noop!(TemplateModule::transfer_from(Origin::signed(3), 2, 4, 1, 1, 600), Error::<Test>::AllowanceExceeded);
+        assert_ok!(TemplateModule::transfer_from(Origin::signed(3), 2, 4, 1, 1, 400));
+        assert_eq!(TemplateModule::fungible_item_id(1, 1).value, 600);
+        assert_eq!(TemplateModule::balance_count(1, 4), 400);
+        assert_eq!(TemplateModule::allowance(1, 1, &2, &3), 100);
+        assert_eq!(TemplateModule::approved(1, 1), [3].to_vec());
+
+        assert_ok!(TemplateModule::transfer_from(Origin::signed(3), 2, 4, 1, 1, 100));
+        assert_eq!(TemplateModule::allowance(1, 1, &2, &3), 0);
+        assert!(TemplateModule::approved(1, 1).is_empty());
+        assert_noop!(TemplateModule::transfer_from(Origin::signed(3), 2, 4, 1, 1, 100), Error::<Test>::NoPermission);
+
+        // new approval replaces the allowance, zero amount removes it
+        assert_ok!(TemplateModule::approve(Origin::signed(2), 3, 1, 1, 50));
+        assert_ok!(TemplateModule::approve(Origin::signed(2), 3, 1, 1, 20));
+        assert_eq!(TemplateModule::allowance(1, 1, &2, &3), 20);
+        assert_ok!(TemplateModule::approve(Origin::signed(2), 3, 1, 1, 0));
+        assert_eq!(TemplateModule::allowance(1, 1, &2, &3), 0);
+        assert!(TemplateModule::approved(1, 1).is_empty());
+    });
+}
+
+#[test]
+fn refungible_allowance_is_per_owner() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_test_collection(&CollectionMode::ReFungible(2000, 3), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 1));
+        assert_ok!(TemplateModule::transfer(Origin::signed(1), 2, 1, 1, 400));
+
+        assert_ok!(TemplateModule::approve(Origin::signed(1), 3, 1, 1, 100));
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::Approved(1, 1, 1, 3, 100))));
+
+        // allowance of one owner does not cover shares of others
+        assert_noop!(TemplateModule::transfer_from(Origin::signed(3), 2, 3, 1, 1, 100), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::transfer_from(Origin::signed(3), 1, 3, 1, 1, 101), Error::<Test>::AllowanceExceeded);
+        assert_ok!(TemplateModule::transfer_from(Origin::signed(3), 1, 3, 1, 1, 60));
+        assert_eq!(TemplateModule::balance_count(1, 3), 60);
+        assert_eq!(TemplateModule::allowance(1, 1, &1, &3), 40);
+
+        // clearing removes allowances of the sender only
+        assert_ok!(TemplateModule::approve(Origin::signed(2), 3, 1, 1, 50));
+        assert_ok!(TemplateModule::clear_approvals(Origin::signed(1), 1, 1));
+        assert_eq!(TemplateModule::allowance(1, 1, &1, &3), 0);
+        assert_eq!(TemplateModule::allowance(1, 1, &2, &3), 50);
+        assert_eq!(TemplateModule::approved(1, 1), [3].to_vec());
+        assert_ok!(TemplateModule::transfer_from(Origin::signed(3), 2, 3, 1, 1, 50));
+        assert!(TemplateModule::approved(1, 1).is_empty());
+    });
+}
+
+#[test]
+fn approvals_are_kept_revoked_and_cleared() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 2));
+
+        assert_ok!(TemplateModule::approve(Origin::signed(2), 3, 1, 1, 1));
+        assert_ok!(TemplateModule::approve(Origin::signed(2), 4, 1, 1, 1));
+        assert_ok!(TemplateModule::approve(Origin::signed(2), 4, 1, 1, 1));
+        assert_eq!(TemplateModule::approved(1, 1), [3, 4].to_vec());
+
+        assert_noop!(TemplateModule::revoke_approval(Origin::signed(1), 3, 1, 1), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::revoke_approval(Origin::signed(2), 5, 1, 1), Error::<Test>::AddressNotApproved);
+        assert_ok!(TemplateModule::revoke_approval(Origin::signed(2), 3, 1, 1));
+        assert_eq!(TemplateModule::approved(1, 1), [4].to_vec());
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::ApprovalRevoked(1, 1, 2, 3))));
+        assert_noop!(TemplateModule::transfer_from(Origin::signed(3), 2, 3, 1, 1, 1), Error::<Test>::NoPermission);
+
+        assert_noop!(TemplateModule::clear_approvals(Origin::signed(3), 1, 1), Error::<Test>::NoPermission);
+        assert_ok!(TemplateModule::clear_approvals(Origin::signed(2), 1, 1));
+        assert!(TemplateModule::approved(1, 1).is_empty());
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::ApprovalsCleared(1, 1))));
+
+        for approved in 0..MAX_APPROVED_PER_OWNER as u64 {
+            assert_ok!(TemplateModule::approve(Origin::signed(2), 100 + approved, 1, 1, 1));
+        }
+        assert_noop!(TemplateModule::approve(Origin::signed(2), 3, 1, 1, 1), Error::<Test>::ApprovedListLimitExceeded);
+        // approving an already approved account does not need a free slot
+        assert_ok!(TemplateModule::approve(Origin::signed(2), 100, 1, 1, 1));
+    });
+}
+
+#[test]
+fn approval_limit_is_per_owner() {
+    new_test_ext().execute_with(|| {
+        create_test_collection(&CollectionMode::ReFungible(2000, 3), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 2));
+        assert_ok!(TemplateModule::transfer(Origin::signed(2), 3, 1, 1, 400));
+
+        // a co-owner filling its approvals does not block the other owner
+        for approved in 0..MAX_APPROVED_PER_OWNER as u64 {
+            assert_ok!(TemplateModule::approve(Origin::signed(3), 100 + approved, 1, 1, 1));
+        }
+        assert_noop!(TemplateModule::approve(Origin::signed(3), 4, 1, 1, 1), Error::<Test>::ApprovedListLimitExceeded);
+        assert_ok!(TemplateModule::approve(Origin::signed(2), 4, 1, 1, 1));
+        assert_eq!(TemplateModule::approved(1, 1).len(), MAX_APPROVED_PER_OWNER as usize + 1);
+
+        // a spender approved by both owners stays approved until both revoke
+        assert_ok!(TemplateModule::approve(Origin::signed(2), 100, 1, 1, 1));
+        assert_ok!(TemplateModule::revoke_approval(Origin::signed(3), 100, 1, 1));
+        assert!(TemplateModule::approved(1, 1).contains(&100));
+        assert_ok!(TemplateModule::clear_approvals(Origin::signed(2), 1, 1));
+        assert!(!TemplateModule::approved(1, 1).contains(&100));
+        assert!(!TemplateModule::approved(1, 1).contains(&4));
+        assert_eq!(TemplateModule::approved(1, 1).len(), MAX_APPROVED_PER_OWNER as usize - 1);
+        assert_eq!(TemplateModule::allowance(1, 1, &3, &101), 1);
+    });
+}
+
+#[test]
+fn refungible_owner_loses_approvals_with_its_part() {
+    new_test_ext().execute_with(|| {
+        create_test_collection(&CollectionMode::ReFungible(2000, 3), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 2));
+        assert_ok!(TemplateModule::transfer(Origin::signed(2), 3, 1, 1, 400));
+        assert_ok!(TemplateModule::approve(Origin::signed(3), 5, 1, 1, 100));
+        assert_ok!(TemplateModule::approve(Origin::signed(2), 6, 1, 1, 100));
+
+        // a partial transfer keeps the approvals
+        assert_ok!(TemplateModule::transfer(Origin::signed(3), 4, 1, 1, 100));
+        assert_eq!(TemplateModule::allowance(1, 1, &3, &5), 100);
+
+        assert_ok!(TemplateModule::transfer(Origin::signed(3), 7, 1, 1, 300));
+        assert_eq!(TemplateModule::allowance(1, 1, &3, &5), 0);
+        assert_eq!(TemplateModule::approved(1, 1), [6].to_vec());
+        assert_eq!(TemplateModule::allowance(1, 1, &2, &6), 100);
+    });
+}
+
+#[test]
+fn operator_approvals() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        assert_ok!(TemplateModule::create_multiple_items(Origin::signed(1), 1, vec![([1].to_vec(), 2); 3]));
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 2, [1].to_vec(), 2));
+
+        assert_noop!(TemplateModule::set_approval_for_all(Origin::signed(2), 3, 3, true), Error::<Test>::CollectionNotFound);
+        assert_ok!(TemplateModule::set_approval_for_all(Origin::signed(2), 1, 3, true));
+        assert!(TemplateModule::is_approved_for_all(1, &2, &3));
+        assert!(System::events().iter().any(|r| r.event == TestEvent::nft(RawEvent::ApprovalForAll(1, 2, 3, true))));
+
+        // operator approval is not used up and covers only one collection
+        assert_ok!(TemplateModule::transfer_from(Origin::signed(3), 2, 4, 1, 1, 1));
+        assert_ok!(TemplateModule::transfer_from(Origin::signed(3), 2, 3, 1, 2, 1));
+        assert_noop!(TemplateModule::transfer_from(Origin::signed(3), 2, 3, 2, 1, 1), Error::<Test>::NoPermission);
+        // nor items of other owners
+        assert_noop!(TemplateModule::transfer_from(Origin::signed(3), 4, 3, 1, 1, 1), Error::<Test>::NoPermission);
+
+        assert_ok!(TemplateModule::burn_item(Origin::signed(3), 1, 3));
+        assert!(!<NftItemList<Test>>::contains_key(1, 3));
+
+        assert_ok!(TemplateModule::set_approval_for_all(Origin::signed(2), 1, 3, false));
+        assert!(!TemplateModule::is_approved_for_all(1, &2, &3));
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1].to_vec(), 2));
+        assert_noop!(TemplateModule::transfer_from(Origin::signed(3), 2, 3, 1, 4, 1), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::burn_item(Origin::signed(3), 1, 4), Error::<Test>::NoPermission);
+
+        for operator in 0..MAX_OPERATORS_PER_ACCOUNT as u64 {
+            assert_ok!(TemplateModule::set_approval_for_all(Origin::signed(2), 1, 100 + operator, true));
+        }
+        assert_noop!(TemplateModule::set_approval_for_all(Origin::signed(2), 1, 3, true), Error::<Test>::OperatorsLimitExceeded);
+
+        // approvals are removed with the collection
+        assert_ok!(TemplateModule::set_approval_for_all(Origin::signed(2), 2, 3, true));
+        assert_ok!(TemplateModule::destroy_collection(Origin::signed(1), 1));
+        assert!(TemplateModule::operator_approvals(1, 2).is_empty());
+        assert!(TemplateModule::is_approved_for_all(2, &2, &3));
+    });
+}
+
+#[test]
+fn operator_can_not_burn_refungible_item() {
+    new_test_ext().execute_with(|| {
+        create_test_collection(&CollectionMode::ReFungible(2000, 3), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 2));
+        assert_ok!(TemplateModule::transfer(Origin::signed(2), 4, 1, 1, 400));
+        assert_ok!(TemplateModule::set_approval_for_all(Origin::signed(2), 1, 3, true));
+
+        assert_noop!(TemplateModule::burn_item(Origin::signed(3), 1, 1), Error::<Test>::NoPermission);
+
+        // the operator moves the part of the owner instead
+        assert_ok!(TemplateModule::transfer_from(Origin::signed(3), 2, 3, 1, 1, 600));
+        assert_eq!(TemplateModule::balance_count(1, 3), 600);
+        assert_eq!(TemplateModule::balance_count(1, 4), 400);
+    });
+}
+
+#[test]
+fn safe_transfer_from_to_account() {
+    new_test_ext().execute_with(|| {
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 2));
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 2));
+        assert_ok!(TemplateModule::approve(Origin::signed(2), 3, 1, 1, 1));
+
+        // same permissions as transfer_from
+        assert_noop!(TemplateModule::safe_transfer_from(Origin::signed(4), 2, 4, 1, 1, 1, [].to_vec()), Error::<Test>::NoPermission);
+        assert_noop!(TemplateModule::safe_transfer_from(Origin::signed(3), 2, 4, 1, 2, 1, [].to_vec()), Error::<Test>::NoPermission);
+
+        // accounts without contract receive items without a call
+        assert_ok!(TemplateModule::safe_transfer_from(Origin::signed(3), 2, 4, 1, 1, 1, [1,2].to_vec()));
+        assert_eq!(TemplateModule::nft_item_id(1, 1).owner, 4);
+        assert!(TemplateModule::approved(1, 1).is_empty());
+
+        assert_ok!(TemplateModule::safe_transfer_from(Origin::signed(2), 2, 4, 1, 2, 1, [].to_vec()));
+        assert_eq!(TemplateModule::nft_item_id(1, 2).owner, 4);
+        assert_eq!(TemplateModule::balance_count(1, 4), 2);
+    });
+}
+
+#[test]
+fn safe_transfer_from_to_contract() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 2));
+        assert_ok!(TemplateModule::approve(Origin::signed(2), 3, 1, 1, 1));
+        let accepting = instantiate_test_contract("accept_nft");
+        let rejecting = instantiate_test_contract("reject_nft");
+        let looping = instantiate_test_contract("loop_nft");
+
+        // the transfer is reverted if the contract rejects the item or runs out of gas
+        assert_noop!(TemplateModule::safe_transfer_from(Origin::signed(3), 2, rejecting, 1, 1, 1, [].to_vec()), Error::<Test>::ReceiverRejectedItem);
+        assert_noop!(TemplateModule::safe_transfer_from(Origin::signed(3), 2, looping, 1, 1, 1, [].to_vec()), Error::<Test>::ReceiverRejectedItem);
+        assert_eq!(TemplateModule::nft_item_id(1, 1).owner, 2);
+        assert_eq!(TemplateModule::allowance(1, 1, &2, &3), 1);
+
+        assert_ok!(TemplateModule::safe_transfer_from(Origin::signed(3), 2, accepting, 1, 1, 1, [1,2].to_vec()));
+        assert_eq!(TemplateModule::nft_item_id(1, 1).owner, accepting);
+        assert_eq!(TemplateModule::balance_count(1, accepting), 1);
+        assert_eq!(TemplateModule::balance_count(1, 2), 0);
+        assert!(TemplateModule::approved(1, 1).is_empty());
+    });
+}
+
+#[test]
+fn on_nft_received_input() {
+    new_test_ext().execute_with(|| {
+        let input = TemplateModule::on_nft_received_input(&3, &2, 1, 5, 10, [7,8].to_vec());
+        assert_eq!(input[..4], ON_NFT_RECEIVED_SELECTOR[..]);
+
+        let args = <(u64, u64, u64, u64, u64, Vec<u8>)>::decode(&mut &input[4..]).unwrap();
+        assert_eq!(args, (3, 2, 1, 5, 10, [7,8].to_vec()));
+    });
+}
+
+#[test]
+fn storage_migrates_from_v0() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(StorageVersion::get(), STORAGE_VERSION);
+        StorageVersion::put(0);
+
+        // values of storage version 0
+        let name: Vec<u16> = "Test1\0".encode_utf16().collect::<Vec<u16>>();
+        let description: Vec<u16> = "TestDescription1\0".encode_utf16().collect::<Vec<u16>>();
+        let token_prefix: Vec<u8> = b"token_prefix1\0".to_vec();
+        unhashed::put(&<Collection<Test>>::hashed_key_for(1), &(
+            1u64, CollectionMode::NFT(3), AccessMode::WhiteList, 0u32, name.clone(), description.clone(),
+            token_prefix.clone(), 3u32, b"schema".to_vec(), 5u64, 0u64,
+        ));
+        unhashed::put(&<NftItemList<Test>>::hashed_key_for(1, 1), &(1u64, 2u64, [1u8, 2, 3].to_vec()));
+        unhashed::put(&<Collection<Test>>::hashed_key_for(2), &(
+            1u64, CollectionMode::ReFungible(3, 3), AccessMode::Normal, 0u32, name.clone(), description.clone(),
+            token_prefix.clone(), 3u32, b"schema".to_vec(), 0u64, 0u64,
+        ));
+        unhashed::put(&<ReFungibleItemList<Test>>::hashed_key_for(2, 1), &(
+            2u64, [Ownership { owner: 2u64, fraction: 1000 }].to_vec(), [4u8].to_vec(),
+        ));
+        unhashed::put(&<Collection<Test>>::hashed_key_for(3), &(
+            1u64, CollectionMode::Fungible(3), AccessMode::Normal, 0u32, name.clone(), description.clone(),
+            token_prefix.clone(), 0u32, b"schema".to_vec(), 0u64, 0u64,
+        ));
+        <FungibleItemList<Test>>::insert(3, 1, FungibleItemType { collection: 3, owner: 2, value: 500 });
+        <ApprovedList<Test>>::insert(1, 1, [3u64, 4].to_vec());
+        <ApprovedList<Test>>::insert(2, 1, [3u64].to_vec());
+        <ApprovedList<Test>>::insert(3, 1, [4u64].to_vec());
+
+        TemplateModule::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get(), STORAGE_VERSION);
+
+        let collection = TemplateModule::collection(1);
+        assert_eq!(collection.owner, 1);
+        assert_eq!(collection.mode, CollectionMode::NFT(3));
+        assert_eq!(collection.access, AccessMode::WhiteList);
+        assert_eq!(collection.name, name);
+        assert_eq!(collection.description, description);
+        assert_eq!(collection.token_prefix, token_prefix);
+        assert_eq!(collection.custom_data_size, 3);
+        assert_eq!(collection.offchain_schema, b"schema".to_vec());
+        assert_eq!(collection.sponsor, 5);
+        assert_eq!(collection.fee_model, FeeModel::PayAsYouGo);
+        assert_eq!(collection.mint_mode, false);
+        assert_eq!(collection.per_account_mint_limit, 0);
+        assert_eq!(collection.unsponsored_calls, 0);
+        assert_eq!(collection.variable_data_size, 0);
+        assert_eq!(collection.variable_data_mode, VariableDataMode::ItemOwner);
+
+        let item = TemplateModule::nft_item_id(1, 1);
+        assert_eq!(item.owner, 2);
+        assert_eq!(item.const_data, [1, 2, 3].to_vec());
+        assert_eq!(item.variable_data, Vec::<u8>::new());
+
+        let item = TemplateModule::refungible_item_id(2, 1);
+        assert_eq!(item.owner, [Ownership { owner: 2, fraction: 1000 }].to_vec());
+        assert_eq!(item.const_data, [4].to_vec());
+        assert_eq!(item.variable_data, Vec::<u8>::new());
+        assert_eq!(TemplateModule::collection(2).fee_model, FeeModel::UserPaid);
+
+        // approvals become allowances of the owner, re-fungible approvals are dropped
+        assert_eq!(TemplateModule::approved(1, 1), [3, 4].to_vec());
+        assert_eq!(TemplateModule::allowance(1, 1, &2, &3), 1);
+        assert_eq!(TemplateModule::allowance(1, 1, &2, &4), 1);
+        assert!(TemplateModule::approved(2, 1).is_empty());
+        assert_eq!(TemplateModule::allowance(2, 1, &2, &3), 0);
+        assert_eq!(TemplateModule::approved(3, 1), [4].to_vec());
+        assert_eq!(TemplateModule::allowance(3, 1, &2, &4), 500);
+        assert_ok!(TemplateModule::revoke_approval(Origin::signed(2), 3, 1, 1));
+        assert_eq!(TemplateModule::approved(1, 1), [4].to_vec());
+
+        // migrated storage is not migrated again
+        TemplateModule::on_runtime_upgrade();
+        assert_eq!(TemplateModule::nft_item_id(1, 1).const_data, [1, 2, 3].to_vec());
+    });
+}
+
+#[test]
+fn storage_migrates_from_v1() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::put(1);
+
+        // collection of storage version 1 keeps its mint settings
+        let name: Vec<u16> = "Test1\0".encode_utf16().collect::<Vec<u16>>();
+        unhashed::put(&<Collection<Test>>::hashed_key_for(1), &(
+            1u64, CollectionMode::NFT(3), AccessMode::Normal, 0u32, name.clone(), name.clone(),
+            b"prefix".to_vec(), 3u32, b"schema".to_vec(), 5u64, 0u64, true, 5u32,
+        ));
+
+        TemplateModule::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get(), STORAGE_VERSION);
+
+        let collection = TemplateModule::collection(1);
+        assert_eq!(collection.name, name);
+        assert_eq!(collection.sponsor, 5);
+        assert_eq!(collection.mint_mode, true);
+        assert_eq!(collection.per_account_mint_limit, 5);
+        assert_eq!(collection.fee_model, FeeModel::PayAsYouGo);
+        assert_eq!(collection.unsponsored_calls, 0);
+        assert_eq!(collection.variable_data_mode, VariableDataMode::ItemOwner);
+    });
+}
+
+#[test]
+fn storage_migrates_from_v2() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::put(2);
+
+        // collection of storage version 2 keeps its fee model, even if sponsored
+        let name: Vec<u16> = "Test1\0".encode_utf16().collect::<Vec<u16>>();
+        unhashed::put(&<Collection<Test>>::hashed_key_for(1), &(
+            1u64, CollectionMode::NFT(3), AccessMode::Normal, 0u32, name.clone(), name.clone(),
+            b"prefix".to_vec(), 3u32, b"schema".to_vec(), 5u64, 0u64, true, 5u32, FeeModel::PrepaidPlan,
+        ));
+
+        TemplateModule::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get(), STORAGE_VERSION);
+
+        let collection = TemplateModule::collection(1);
+        assert_eq!(collection.sponsor, 5);
+        assert_eq!(collection.mint_mode, true);
+        assert_eq!(collection.per_account_mint_limit, 5);
+        assert_eq!(collection.fee_model, FeeModel::PrepaidPlan);
+        assert_eq!(collection.unsponsored_calls, 0);
+        assert_eq!(collection.variable_data_size, 0);
+    });
+}
+
+#[test]
+fn storage_migrates_from_v3() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::put(3);
+
+        // collection of storage version 3 keeps its unsponsored calls
+        let name: Vec<u16> = "Test1\0".encode_utf16().collect::<Vec<u16>>();
+        unhashed::put(&<Collection<Test>>::hashed_key_for(1), &(
+            1u64, CollectionMode::NFT(3), AccessMode::Normal, 0u32, name.clone(), name.clone(),
+            b"prefix".to_vec(), 3u32, b"schema".to_vec(), 5u64, 0u64, true, 5u32, FeeModel::PrepaidPlan, SPONSOR_TRANSFER,
+        ));
+
+        TemplateModule::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get(), STORAGE_VERSION);
+
+        let collection = TemplateModule::collection(1);
+        assert_eq!(collection.per_account_mint_limit, 5);
+        assert_eq!(collection.fee_model, FeeModel::PrepaidPlan);
+        assert_eq!(collection.unsponsored_calls, SPONSOR_TRANSFER);
+        assert_eq!(collection.variable_data_size, 0);
+        assert_eq!(collection.variable_data_mode, VariableDataMode::ItemOwner);
+    });
+}
+
+#[test]
+fn storage_migrates_from_v4() {
+    new_test_ext().execute_with(|| {
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 2));
+        StorageVersion::put(4);
+
+        // approvals of storage version 4 have no allowances
+        <ApprovedList<Test>>::insert(1, 1, [3u64].to_vec());
+
+        TemplateModule::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get(), STORAGE_VERSION);
+        assert_eq!(TemplateModule::approved(1, 1), [3].to_vec());
+        assert_eq!(TemplateModule::allowance(1, 1, &2, &3), 1);
+        assert_ok!(TemplateModule::transfer_from(Origin::signed(3), 2, 4, 1, 1, 1));
+        assert_eq!(TemplateModule::nft_item_id(1, 1).owner, 4);
+    });
+}
+
+#[test]
+fn resource_quota_is_not_used_by_denied_calls() {
+    new_test_ext().execute_with(|| {
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        assert_ok!(TemplateModule::create_item(Origin::signed(1), 1, [1,2,3].to_vec(), 2));
+        assert_ok!(TemplateModule::select_fee_model(Origin::signed(1), 1, FeeModel::ResourcePurchase));
+        assert_ok!(TemplateModule::buy_resources(Origin::signed(1), 1, 1));
+
+        // account 3 does not own the item, the transfer would fail
+        charge_test_call(3, &Call::TemplateModule(crate::Call::transfer(4, 1, 1, 1)));
+        assert_eq!(Balances::free_balance(3), 1_000_000 - TEST_CALL_ACTUAL_FEE);
+        assert_eq!(TemplateModule::resource_quota(1), 1);
+
+        // account 3 can not create items in the collection
+        charge_test_call(3, &Call::TemplateModule(crate::Call::create_item(1, [1,2,3].to_vec(), 3)));
+        assert_eq!(Balances::free_balance(3), 1_000_000 - 2 * TEST_CALL_ACTUAL_FEE);
+        assert_eq!(TemplateModule::resource_quota(1), 1);
+
+        // the item owner uses the quota
+        charge_test_call(2, &Call::TemplateModule(crate::Call::transfer(4, 1, 1, 1)));
+        assert_eq!(Balances::free_balance(2), 1_000_000);
+        assert_eq!(TemplateModule::resource_quota(1), 0);
+    });
+}
+
+#[test]
+fn create_multiple_items_counts_every_item() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_test_collection(&CollectionMode::NFT(2000), 1);
+        let items = |count: u64| Call::TemplateModule(crate::Call::create_multiple_items(1, (0..count).map(|_| ([1,2,3].to_vec(), 1)).collect()));
+
+        // every created item uses one transaction of the quota
+        assert_ok!(TemplateModule::select_fee_model(Origin::signed(1), 1, FeeModel::ResourcePurchase));
+        assert_ok!(TemplateModule::buy_resources(Origin::signed(1), 1, 2));
+        let balance = Balances::free_balance(1);
+        charge_test_call(1, &items(3));
+        assert_eq!(Balances::free_balance(1), balance - TEST_CALL_ACTUAL_FEE);
+        assert_eq!(TemplateModule::resource_quota(1), 2);
+        charge_test_call(1, &items(2));
+        assert_eq!(Balances::free_balance(1), balance - TEST_CALL_ACTUAL_FEE);
+        assert_eq!(TemplateModule::resource_quota(1), 0);
+
+        // every created item delays the next sponsored creation by the timeout
+        assert_ok!(TemplateModule::set_collection_sponsor(Origin::signed(1), 1, 5));
+        assert_ok!(TemplateModule::confirm_sponsorship(Origin::signed(5), 1));
+        assert_ok!(TemplateModule::select_fee_model(Origin::signed(1), 1, FeeModel::PayAsYouGo));
+        let limits = SponsorshipLimitsType { create_item_timeout: 10, ..Default::default() };
+        assert_ok!(TemplateModule::set_sponsorship_limits(Origin::signed(1), 1, limits));
+        charge_test_call(1, &items(3));
+        assert_eq!(Balances::free_balance(5), 1_000_000 - TEST_CALL_ACTUAL_FEE);
+        assert_eq!(TemplateModule::create_item_sponsor_basket(1, 1), Some(21));
+
+        System::set_block_number(30);
+        assert_eq!(TemplateModule::collection_fee_payer(1, &1, None, 100), 0);
+        System::set_block_number(31);
+        assert_eq!(TemplateModule::collection_fee_payer(1, &1, None, 100), 5);
+    });
+}
+
 // #[test]
 // fn create_collection_test() {
 //     new_test_ext().execute_with(|| {
@@ -44,8 +44,10 @@ use system::{self as system};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
 use sp_runtime::{
-	Perbill,
+	Perbill, ModuleId, ConsensusEngineId, traits::AccountIdConversion,
 };
+use frame_support::traits::FindAuthor;
+use codec::Decode;
 
 
 pub use timestamp::Call as TimestampCall;
@@ -108,10 +110,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("nft"),
     impl_name: create_runtime_str!("nft"),
     authoring_version: 1,
-    spec_version: 1,
+    spec_version: 2,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 2,
 };
 
 pub const MILLISECS_PER_BLOCK: u64 = 6000;
@@ -281,11 +283,32 @@ impl contracts::Trait for Runtime {
 
 parameter_types! {
     pub const TransactionByteFee: Balance = 1;
+    pub const TreasuryModuleId: ModuleId = ModuleId(*b"py/trsry");
+    pub TreasuryAccount: AccountId = TreasuryModuleId::get().into_account();
+    /// Share of transaction fees that goes to the network owner, the rest goes to block author.
+    pub const TreasuryFeeShare: Perbill = Perbill::from_percent(20);
+    pub const TreasuryResourceShare: Perbill = Perbill::from_percent(100);
 }
 
+/// Account of the Aura authority that authored the block.
+pub struct AuraAccountAdapter;
+
+impl FindAuthor<AccountId> for AuraAccountAdapter {
+    fn find_author<'a, I>(digests: I) -> Option<AccountId>
+        where I: 'a + IntoIterator<Item=(ConsensusEngineId, &'a [u8])>
+    {
+        let index = Aura::find_author(digests)? as usize;
+        let authority = Aura::authorities().get(index)?.clone();
+        let mut raw: &[u8] = authority.as_ref();
+        AccountId::decode(&mut raw).ok()
+    }
+}
+
+pub type DealWithFees = nft::FeeSplit<Runtime, TreasuryAccount, TreasuryFeeShare, AuraAccountAdapter>;
+
 impl transaction_payment::Trait for Runtime {
     type Currency = balances::Module<Runtime>;
-    type OnTransactionPayment = ();
+    type OnTransactionPayment = DealWithFees;
     type TransactionByteFee = TransactionByteFee;
     type WeightToFee = IdentityFee<Balance>;
     type FeeMultiplierUpdate = ();
@@ -296,9 +319,25 @@ impl sudo::Trait for Runtime {
     type Call = Call;
 }
 
+parameter_types! {
+    pub const NftModuleId: ModuleId = ModuleId(*b"py/nftdp");
+    pub const ResourceTxPrice: Balance = 10 * MILLICENTS;
+    /// Creating collection costs 1 Unique in addition to the weight fee.
+    pub const CollectionCreationFee: Balance = 1_000_000_000;
+    /// Recipient contracts get 100 ms to accept an item.
+    pub const OnNftReceivedGasLimit: Weight = WEIGHT_PER_SECOND / 10;
+}
+
 /// Used for the module nft in `./nft.rs`
 impl nft::Trait for Runtime {
     type Event = Event;
+    type ModuleId = NftModuleId;
+    type ResourceTxPrice = ResourceTxPrice;
+    type OnResourcePayment = nft::FeeSplit<Runtime, TreasuryAccount, TreasuryResourceShare, AuraAccountAdapter>;
+    type CollectionCreationFee = CollectionCreationFee;
+    type OnNftReceivedGasLimit = OnNftReceivedGasLimit;
+    type Contracts = nft::ContractsAdapter<Runtime>;
+    type WeightInfo = ();
 }
 
 construct_runtime!(
@@ -316,7 +355,7 @@ construct_runtime!(
         Balances: balances::{Module, Call, Storage, Config<T>, Event<T>},
         TransactionPayment: transaction_payment::{Module, Storage},
         Sudo: sudo::{Module, Call, Config<T>, Storage, Event<T>},
-        Nft: nft::{Module, Call, Storage, Event<T>},
+        Nft: nft::{Module, Call, Storage, Event<T>, Config},
     }
 );
 
@@ -385,6 +424,16 @@ impl_runtime_apis! {
         }
     }
 
+    impl nft_runtime_api::NftApi<Block, AccountId> for Runtime {
+        fn resource_quota(collection_id: u64) -> u64 {
+            Nft::resource_quota(collection_id)
+        }
+
+        fn allowance(collection_id: u64, item_id: u64, owner: AccountId, spender: AccountId) -> u64 {
+            Nft::allowance(collection_id, item_id, &owner, &spender)
+        }
+    }
+
     impl sp_api::Core<Block> for Runtime {
         fn version() -> RuntimeVersion {
             VERSION
@@ -467,6 +516,28 @@ impl_runtime_apis! {
         }
     }
 
+    #[cfg(feature = "runtime-benchmarks")]
+    impl frame_benchmarking::Benchmark<Block> for Runtime {
+        fn dispatch_benchmark(
+            pallet: Vec<u8>,
+            benchmark: Vec<u8>,
+            lowest_range_values: Vec<u32>,
+            highest_range_values: Vec<u32>,
+            steps: Vec<u32>,
+            repeat: u32,
+        ) -> Result<Vec<frame_benchmarking::BenchmarkBatch>, sp_runtime::RuntimeString> {
+            use frame_benchmarking::{Benchmarking, BenchmarkBatch, add_benchmark};
+
+            let mut batches = Vec::<BenchmarkBatch>::new();
+            let params = (&pallet, &benchmark, &lowest_range_values, &highest_range_values, &steps, repeat);
+
+            add_benchmark!(params, batches, b"nft", Nft);
+
+            if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
+            Ok(batches)
+        }
+    }
+
     impl fg_primitives::GrandpaApi<Block> for Runtime {
         fn grandpa_authorities() -> GrandpaAuthorityList {
             Grandpa::grandpa_authorities()
@@ -17,7 +17,8 @@ mod calls {
     pub struct NftItemType {
         pub collection: u64,
         pub owner: AccountId,
-        pub data: Vec<u8>,
+        pub const_data: Vec<u8>,
+        pub variable_data: Vec<u8>,
     }
 
     /// This simple dummy contract dispatches substrate runtime calls
@@ -47,13 +48,13 @@ mod calls {
         // SafeTransfer
 
         #[ink(message)]
-        fn approve(&self, approved: AccountId, collection_id: u64, item_id: u64) {
+        fn approve(&self, approved: AccountId, collection_id: u64, item_id: u64, amount: u64) {
             env::println(&format!(
-                "approve invoke_runtime params {:?}, {:?}, {:?} ",
-                approved, collection_id, item_id
+                "approve invoke_runtime params {:?}, {:?}, {:?}, {:?} ",
+                approved, collection_id, item_id, amount
             ));
 
-            let approve_call = runtime_calls::approve(approved, collection_id, item_id);
+            let approve_call = runtime_calls::approve(approved, collection_id, item_id, amount);
             // dispatch the call to the runtime
             let result = self.env().invoke_runtime(&approve_call);
 
@@ -122,7 +123,18 @@ mod calls {
             env::println(&format!("transfer_from invoke_runtime result {:?}", result));
         }
 
-        // SafeTransferFrom
+        /// Accepts items sent to the contract with SafeTransferFrom by returning
+        /// the selector of this message
+        #[ink(message)]
+        fn on_nft_received(&self, operator: AccountId, from: AccountId, collection_id: u64, item_id: u64, value: u64, data: Vec<u8>) -> [u8; 4] {
+            env::println(&format!(
+                "on_nft_received params {:?}, {:?}, {:?}, {:?}, {:?}, {:?} ",
+                operator, from, collection_id, item_id, value, data
+            ));
+
+            [0x91, 0xb2, 0xa4, 0x98]
+        }
+
         #[ink(message)]
         fn create_item(&self, collection_id: u64, properties: RawData, owner: AccountId) {
             env::println(&format!(
@@ -70,13 +70,13 @@ where
     transfer(T::AccountId, u64, u64, u64),
 
     #[allow(non_camel_case_types)]
-    nft_approve(T::AccountId, u64, u64),
+    nft_approve(T::AccountId, u64, u64, u64),
 
     #[allow(non_camel_case_types)]
     nft_transfer_from(T::AccountId, u64, u64, u64),
 
     #[allow(non_camel_case_types)]
-    nft_safe_transfer(u64, u64, T::AccountId),
+    nft_safe_transfer_from(T::AccountId, T::AccountId, u64, u64, u64, Vec<u8>),
 
     #[allow(non_camel_case_types)]
     set_offchain_schema(u64, Vec<u8>),
@@ -101,12 +101,12 @@ pub fn create_collection(
     .into()
 }
 
-// pub fn safe_transfer(collection_id: u64, item_id: u64, new_owner: AccountId) -> Call {
-//     Nft::<NodeRuntimeTypes>::nft_safe_transfer(collection_id, item_id, new_owner.into()).into()
+// pub fn safe_transfer_from(from: AccountId, recipient: AccountId, collection_id: u64, item_id: u64, value: u64, data: Vec<u8>) -> Call {
+//     Nft::<NodeRuntimeTypes>::nft_safe_transfer_from(from.into(), recipient.into(), collection_id, item_id, value, data).into()
 // }
 
-pub fn approve(approved: AccountId, collection_id: u64, item_id: u64) -> Call {
-    Nft::<NodeRuntimeTypes>::nft_approve(approved.into(), collection_id, item_id).into()
+pub fn approve(approved: AccountId, collection_id: u64, item_id: u64, amount: u64) -> Call {
+    Nft::<NodeRuntimeTypes>::nft_approve(approved.into(), collection_id, item_id, amount).into()
 }
 
 pub fn transfer_from(new_owner: AccountId, collection_id: u64, item_id: u64, value: u64) -> Call {